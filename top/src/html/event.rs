@@ -1,5 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
 
+use log::warn;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -9,8 +11,53 @@ use crate::html::Html;
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Event {
-    Update { id: Uuid, value: String },
-    Press { id: Uuid },
+    Update {
+        id: Uuid,
+        value: String,
+    },
+    Press {
+        id: Uuid,
+    },
+    /// A chunk of a file chosen in a file input, starting at `offset` in a file of `size` bytes.
+    /// Files are sent in chunks as binary messages instead of text, see [`Event::from_binary`]. The
+    /// bytes are shared, so passing the event on to several tasks does not copy them.
+    #[serde(skip)]
+    Upload {
+        id: Uuid,
+        name: String,
+        mime: String,
+        size: usize,
+        offset: usize,
+        bytes: Arc<[u8]>,
+    },
+}
+
+/// The header of a binary message containing a chunk of an uploaded file.
+#[derive(Deserialize)]
+struct UploadHeader {
+    id: Uuid,
+    name: String,
+    mime: String,
+    size: usize,
+    offset: usize,
+}
+
+impl Event {
+    /// Reads an [`Event::Upload`] from a binary message, which consists of a JSON header with the
+    /// `id` of the input, the `name`, `mime` type and `size` of the file and the `offset` of the
+    /// chunk, followed by a zero byte and the content of the chunk.
+    pub fn from_binary(message: &[u8]) -> Option<Self> {
+        let separator = message.iter().position(|byte| *byte == 0)?;
+        let header: UploadHeader = serde_json::from_slice(&message[..separator]).ok()?;
+        Some(Event::Upload {
+            id: header.id,
+            name: header.name,
+            mime: header.mime,
+            size: header.size,
+            offset: header.offset,
+            bytes: Arc::from(&message[separator + 1..]),
+        })
+    }
 }
 
 /// Changes to the user interface in response to [`Event`]s, such as confirming a value is valid, or
@@ -26,6 +73,8 @@ pub enum Change {
     AppendContent { id: Uuid, html: Html },
     /// Remove this element.
     Remove { id: Uuid },
+    /// Swap the positions of this element and another one.
+    Swap { id: Uuid, other: Uuid },
     /// The value of this html is valid.
     Valid { id: Uuid },
     /// The value of this html is invalid.
@@ -42,6 +91,7 @@ impl Change {
             | Change::Replace { id, .. }
             | Change::AppendContent { id, .. }
             | Change::Remove { id, .. }
+            | Change::Swap { id, .. }
             | Change::Valid { id, .. }
             | Change::Invalid { id, .. }
             | Change::UpdateValue { id, .. } => *id,
@@ -54,13 +104,17 @@ impl Change {
                 Change::ReplaceContent { html: other, .. } => *html = other,
                 Change::Replace { .. } | Change::Remove { .. } => *self = other,
                 Change::AppendContent { html: other, .. } => html.0.push_str(&other.0),
-                Change::Valid { .. } | Change::Invalid { .. } | Change::UpdateValue { .. } => {
+                Change::Swap { .. }
+                | Change::Valid { .. }
+                | Change::Invalid { .. }
+                | Change::UpdateValue { .. } => {
                     return Err(());
                 }
             },
             Change::Replace { .. } => match other {
                 Change::ReplaceContent { .. }
                 | Change::AppendContent { .. }
+                | Change::Swap { .. }
                 | Change::Valid { .. }
                 | Change::Invalid { .. }
                 | Change::UpdateValue { .. } => {
@@ -73,24 +127,39 @@ impl Change {
                     *self = other
                 }
                 Change::AppendContent { html: other, .. } => html.0.push_str(&other.0),
-                Change::Valid { .. } | Change::Invalid { .. } | Change::UpdateValue { .. } => {
+                Change::Swap { .. }
+                | Change::Valid { .. }
+                | Change::Invalid { .. }
+                | Change::UpdateValue { .. } => {
                     return Err(());
                 }
             },
             Change::Remove { .. } => match other {
                 Change::Replace { .. }
                 | Change::ReplaceContent { .. }
-                | Change::AppendContent { .. } => return Err(()),
+                | Change::AppendContent { .. }
+                | Change::Swap { .. } => return Err(()),
                 Change::Remove { .. } => {}
                 Change::Valid { .. } | Change::Invalid { .. } | Change::UpdateValue { .. } => {
                     return Err(());
                 }
             },
+            Change::Swap { .. } => match other {
+                Change::Remove { .. } => *self = other,
+                Change::ReplaceContent { .. }
+                | Change::Replace { .. }
+                | Change::AppendContent { .. }
+                | Change::Swap { .. }
+                | Change::Valid { .. }
+                | Change::Invalid { .. }
+                | Change::UpdateValue { .. } => return Err(()),
+            },
             Change::Valid { .. } | Change::Invalid { .. } => *self = other,
             Change::UpdateValue { value, .. } => match other {
                 Change::ReplaceContent { .. }
                 | Change::Replace { .. }
-                | Change::AppendContent { .. } => return Err(()),
+                | Change::AppendContent { .. }
+                | Change::Swap { .. } => return Err(()),
                 Change::Remove { .. } => *self = other,
                 Change::Valid { .. } | Change::Invalid { .. } => {}
                 Change::UpdateValue { value: other, .. } => *value = other,
@@ -144,10 +213,37 @@ impl Feedback {
         Ok(self)
     }
 
+    /// Combines two pieces of feedback like [`Feedback::merged_with`], but instead of failing when
+    /// the changes to an element conflict, the change from [`other`] replaces the one from this
+    /// feedback.
+    pub fn combined_with(mut self, mut other: Self) -> Self {
+        for (id, other) in other.changes {
+            match self.changes.get_mut(&id) {
+                None => {
+                    self.changes.insert(id, other);
+                }
+                Some(change) => {
+                    if change.merge_with(other.clone()).is_err() {
+                        warn!("conflicting changes to element {id}");
+                        *change = other;
+                    }
+                }
+            }
+        }
+        self.shares.append(&mut other.shares);
+
+        self
+    }
+
     pub fn changes(self) -> Vec<Change> {
         self.changes.into_values().collect()
     }
 
+    /// The change made to the element with this `id`, if any.
+    pub fn get(&self, id: &Uuid) -> Option<&Change> {
+        self.changes.get(id)
+    }
+
     pub fn shares(&self) -> &BTreeSet<Uuid> {
         &self.shares
     }
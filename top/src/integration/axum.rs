@@ -1,7 +1,6 @@
 use std::collections::BTreeSet;
 use std::convert::Infallible;
 use std::task::Poll;
-use std::time::Duration;
 
 use axum::body::Body;
 use axum::extract::ws::{Message, WebSocket};
@@ -12,13 +11,14 @@ use axum::routing::{get, get_service, MethodRouter};
 use futures::stream::SplitSink;
 use futures::{SinkExt, StreamExt};
 use log::{error, warn};
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::broadcast::Receiver;
 use tower_http::services::ServeFile;
 use tower_service::Service;
 use uuid::Uuid;
 
-use tokio::time::timeout;
-
-use crate::html::event::{Change, Feedback};
+use crate::html::event::{Change, Event, Feedback};
+use crate::share;
 use crate::task::Task;
 
 #[derive(Clone, Debug)]
@@ -98,6 +98,7 @@ where
 {
     ws.on_upgrade(|socket| async move {
         let (mut sender, mut receiver) = socket.split();
+        let mut updates = share::subscribe();
 
         // Initial page
         let html = task.to_html().await;
@@ -107,32 +108,53 @@ where
         });
         send_feedback(&mut sender, feedback).await;
 
-        // Respond to input
+        // Respond to input and to updates of shares
         loop {
-            match timeout(Duration::from_secs(1), receiver.next()).await {
-                // Received message
-                Ok(Some(Ok(message))) => match message.into_text() {
-                    Ok(text) => match serde_json::from_str(&text) {
-                        Ok(event) => {
+            tokio::select! {
+                message = receiver.next() => match message {
+                    // Received message
+                    Some(Ok(message)) => {
+                        if let Some(event) = read_event(message) {
                             let mut feedback = task.on_event(event).await;
-                            let ids = feedback.shares().clone();
-                            let refresh = task.refresh(&ids).await;
+                            // Shares updated by the event are refreshed along with the others
+                            let mut ids = feedback.shares().clone();
+                            let refresh = match receive_updates(&mut updates, &mut ids) {
+                                Some(false) => task.refresh(&ids).await,
+                                Some(true) => refresh_all(&mut task).await,
+                                None => {
+                                    error!("share updates were closed");
+                                    return;
+                                }
+                            };
                             feedback = feedback.merged_with(refresh).unwrap();
                             if !feedback.is_empty() {
                                 send_feedback(&mut sender, feedback).await;
                             }
                         }
-                        Err(_) => warn!("not an event"),
-                    },
-                    Err(_) => warn!("non-text message"),
+                    }
+                    // Received error
+                    Some(Err(_)) => warn!("something went wrong"),
+                    // Stream closed
+                    None => return,
                 },
-                // Received error
-                Ok(Some(Err(_))) => warn!("something went wrong"),
-                // Stream closed
-                Ok(None) => return,
-                // Timeout, update shares
-                Err(_) => {
-                    let feedback = task.refresh(&BTreeSet::new()).await;
+                update = updates.recv() => {
+                    let mut ids = BTreeSet::new();
+                    let all = match update {
+                        Ok(id) => {
+                            ids.insert(id);
+                            receive_updates(&mut updates, &mut ids)
+                        }
+                        Err(RecvError::Lagged(_)) => Some(true),
+                        Err(RecvError::Closed) => None,
+                    };
+                    let feedback = match all {
+                        Some(false) => task.refresh(&ids).await,
+                        Some(true) => refresh_all(&mut task).await,
+                        None => {
+                            error!("share updates were closed");
+                            return;
+                        }
+                    };
                     if !feedback.is_empty() {
                         send_feedback(&mut sender, feedback).await;
                     }
@@ -142,6 +164,57 @@ where
     })
 }
 
+/// Adds the ids of the shares that were updated since the last time to `ids`, without waiting.
+/// Returns whether updates were missed, in which case everything has to be refreshed, or `None` if
+/// no more updates can be received.
+fn receive_updates(updates: &mut Receiver<Uuid>, ids: &mut BTreeSet<Uuid>) -> Option<bool> {
+    loop {
+        match updates.try_recv() {
+            Ok(id) => {
+                ids.insert(id);
+            }
+            Err(TryRecvError::Empty) => return Some(false),
+            Err(TryRecvError::Lagged(_)) => return Some(true),
+            Err(TryRecvError::Closed) => return None,
+        }
+    }
+}
+
+/// Refreshes the task with all shares, and renders the whole task again, when it is not known which
+/// shares were updated.
+async fn refresh_all<T>(task: &mut T) -> Feedback
+where
+    T: Task,
+{
+    // The task is rendered again as a whole, so only its state has to be brought up to date
+    let _ = task.refresh(&BTreeSet::from([share::ALL])).await;
+    Feedback::from(Change::ReplaceContent {
+        id: Uuid::nil(),
+        html: task.to_html().await,
+    })
+}
+
+/// Reads an event from a text message, or an upload from a binary message.
+fn read_event(message: Message) -> Option<Event> {
+    match message {
+        Message::Text(text) => match serde_json::from_str(&text) {
+            Ok(event) => Some(event),
+            Err(_) => {
+                warn!("not an event");
+                None
+            }
+        },
+        Message::Binary(data) => match Event::from_binary(&data) {
+            Some(event) => Some(event),
+            None => {
+                warn!("not an upload");
+                None
+            }
+        },
+        Message::Ping(_) | Message::Pong(_) | Message::Close(_) => None,
+    }
+}
+
 async fn send_feedback(sender: &mut SplitSink<WebSocket, Message>, feedback: Feedback) {
     match serde_json::to_string(&feedback.changes()) {
         Ok(text) => sender
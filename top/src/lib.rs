@@ -4,3 +4,11 @@ pub mod html;
 pub mod integration;
 pub mod share;
 pub mod task;
+
+#[doc(hidden)]
+pub mod __private {
+    //! Re-exports used by the code generated by `top_derive`.
+
+    pub use async_trait::async_trait;
+    pub use uuid::Uuid;
+}
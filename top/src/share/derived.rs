@@ -0,0 +1,143 @@
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::share::{ShareRead, ShareUpdate};
+use crate::task::TaskValue;
+
+/// Share computed from another share by applying a function to its value, created using
+/// [`ShareRead::map`]. It is updated whenever the share it is computed from is.
+pub struct ShareMapped<S, F> {
+    id: Uuid,
+    share: S,
+    f: Arc<F>,
+}
+
+impl<S, F> ShareMapped<S, F> {
+    pub(crate) fn new(share: S, f: F) -> Self {
+        ShareMapped {
+            id: Uuid::new_v4(),
+            share,
+            f: Arc::new(f),
+        }
+    }
+}
+
+impl<S, F> Clone for ShareMapped<S, F>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        ShareMapped {
+            id: self.id,
+            share: self.share.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<S, F> Debug for ShareMapped<S, F>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShareMapped")
+            .field("id", &self.id)
+            .field("share", &self.share)
+            .finish_non_exhaustive()
+    }
+}
+
+pub struct DerivedWrapper<T>(pub(crate) TaskValue<T>);
+
+impl<T> AsRef<TaskValue<T>> for DerivedWrapper<T> {
+    fn as_ref(&self) -> &TaskValue<T> {
+        &self.0
+    }
+}
+
+impl<S, F, T> ShareRead for ShareMapped<S, F>
+where
+    S: ShareRead,
+    F: Fn(&S::Value) -> T,
+{
+    type Value = T;
+    type Read<'a> = DerivedWrapper<T> where S: 'a, F: 'a;
+
+    fn read<'a>(&'a self) -> Self::Read<'a> {
+        let value = match self.share.read().as_ref() {
+            TaskValue::Stable(value) => TaskValue::Stable((self.f)(value)),
+            TaskValue::Unstable(value) => TaskValue::Unstable((self.f)(value)),
+            TaskValue::Error(error) => TaskValue::Error(error.clone()),
+            TaskValue::Empty => TaskValue::Empty,
+        };
+        DerivedWrapper(value)
+    }
+}
+
+impl<S, F> ShareUpdate for ShareMapped<S, F>
+where
+    S: ShareUpdate,
+{
+    /// The id of the computed share itself, which is never updated since it cannot be written.
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        self.share.updated(ids)
+    }
+}
+
+/// Share combining the values of two shares into a pair, created using [`ShareRead::zip`]. It is
+/// updated whenever either of the shares is.
+#[derive(Clone, Debug)]
+pub struct ShareZip<A, B> {
+    id: Uuid,
+    first: A,
+    second: B,
+}
+
+impl<A, B> ShareZip<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        ShareZip {
+            id: Uuid::new_v4(),
+            first,
+            second,
+        }
+    }
+}
+
+impl<A, B> ShareRead for ShareZip<A, B>
+where
+    A: ShareRead,
+    A::Value: Clone,
+    B: ShareRead,
+    B::Value: Clone,
+{
+    type Value = (A::Value, B::Value);
+    type Read<'a> = DerivedWrapper<(A::Value, B::Value)> where A: 'a, B: 'a;
+
+    fn read<'a>(&'a self) -> Self::Read<'a> {
+        let first = self.first.read().as_ref().clone();
+        let second = self.second.read().as_ref().clone();
+        DerivedWrapper(first.and(second))
+    }
+}
+
+impl<A, B> ShareUpdate for ShareZip<A, B>
+where
+    A: ShareUpdate,
+    B: ShareUpdate,
+{
+    /// The id of the combined share itself, which is never updated since it cannot be written.
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        self.first.updated(ids) || self.second.updated(ids)
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::share::value::ShareGuard;
+use crate::share::{is_updated, notify, ShareRead, ShareUpdate, ShareWrite};
+use crate::task::{OptionExt, TaskValue};
+
+/// When a [`ShareFile`] writes its value to its file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Flush {
+    /// Writes the file whenever the share is written.
+    Always,
+    /// Writes the file when the share is written, but at most once per interval. Changes made in
+    /// the meantime are written along with the next one once the interval has passed.
+    Interval(Duration),
+    /// Only writes the file when [`ShareFile::flush`] is called.
+    Manual,
+}
+
+/// Shares a value which is kept in a JSON file, so it survives restarts. The file is read when the
+/// share is opened, and is replaced as a whole when it is written according to its [`Flush`]
+/// policy. Files are written by a background thread, so writing the share does not wait for the
+/// file. Changes that are not written yet are written when the last clone of the share is dropped.
+///
+/// Empty values are stored as `null`. Errors are not stored, so the file keeps the last value that
+/// was written before.
+#[derive(Debug)]
+pub struct ShareFile<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    id: Uuid,
+    state: Arc<FileState<T>>,
+}
+
+#[derive(Debug)]
+struct FileState<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    path: PathBuf,
+    flush: Flush,
+    value: Mutex<TaskValue<T>>,
+    status: Mutex<FileStatus>,
+    /// Wakes up the thread that writes the file, unless it is only written manually.
+    written: Option<Sender<()>>,
+}
+
+#[derive(Debug)]
+struct FileStatus {
+    dirty: bool,
+    flushed: Instant,
+}
+
+impl<T> ShareFile<T>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+{
+    /// Opens the share kept in the file at `path`, which starts out with `value` if the file does
+    /// not exist yet. The file is written after every write.
+    pub fn open(path: impl Into<PathBuf>, value: Option<T>) -> io::Result<Self> {
+        Self::open_with_flush(path, value, Flush::Always)
+    }
+
+    /// Opens the share kept in the file at `path` like [`ShareFile::open`], writing the file
+    /// according to `flush`.
+    pub fn open_with_flush(
+        path: impl Into<PathBuf>,
+        value: Option<T>,
+        flush: Flush,
+    ) -> io::Result<Self> {
+        let path = path.into();
+        let value = match fs::read(&path) {
+            Ok(content) => serde_json::from_slice::<Option<T>>(&content)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => value,
+            Err(error) => return Err(error),
+        };
+        let (written, receiver) = match flush {
+            Flush::Always | Flush::Interval(_) => {
+                let (sender, receiver) = mpsc::channel();
+                (Some(sender), Some(receiver))
+            }
+            Flush::Manual => (None, None),
+        };
+        let state = Arc::new(FileState {
+            path,
+            flush,
+            value: Mutex::new(value.into_unstable()),
+            status: Mutex::new(FileStatus {
+                dirty: false,
+                flushed: Instant::now(),
+            }),
+            written,
+        });
+        if let Some(receiver) = receiver {
+            FileState::flush_in_background(Arc::downgrade(&state), receiver)?;
+        }
+        Ok(ShareFile {
+            id: Uuid::new_v4(),
+            state,
+        })
+    }
+}
+
+impl<T> ShareFile<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    pub fn path(&self) -> &Path {
+        &self.state.path
+    }
+
+    /// Writes the value to the file, if it changed since the file was last written.
+    pub fn flush(&self) -> io::Result<()> {
+        self.state.flush(&mut self.state.status.lock().unwrap())
+    }
+}
+
+impl<T> FileState<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn flush(&self, status: &mut FileStatus) -> io::Result<()> {
+        if !status.dirty {
+            return Ok(());
+        }
+        let content = match &*self.value.lock().unwrap() {
+            TaskValue::Stable(value) | TaskValue::Unstable(value) => {
+                Some(serde_json::to_vec(value)?)
+            }
+            TaskValue::Empty => Some(serde_json::to_vec(&None::<T>)?),
+            TaskValue::Error(_) => None,
+        };
+        if let Some(content) = content {
+            self.replace(&content)?;
+        }
+        status.dirty = false;
+        status.flushed = Instant::now();
+        Ok(())
+    }
+
+    /// Replaces the content of the file by writing it to a temporary file next to it first, so the
+    /// file never contains only part of a value. The temporary file has a name of its own, so
+    /// shares that were opened more than once do not write to the same temporary file.
+    fn replace(&self, content: &[u8]) -> io::Result<()> {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}.tmp", Uuid::new_v4()));
+        let temporary = self.path.with_file_name(name);
+        let written = fs::File::create(&temporary).and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()?;
+            fs::rename(&temporary, &self.path)
+        });
+        if let Err(error) = written {
+            let _ = fs::remove_file(&temporary);
+            return Err(error);
+        }
+        // The rename itself is only durable once the directory containing the file is synced
+        #[cfg(unix)]
+        {
+            let directory = match self.path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            fs::File::open(directory)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> FileState<T>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+{
+    /// Writes changes to the file from a background thread, which is woken up by `written` after
+    /// each write, until the share is dropped.
+    fn flush_in_background(state: Weak<Self>, written: Receiver<()>) -> io::Result<()> {
+        thread::Builder::new()
+            .name("top-share-file".to_owned())
+            .spawn(move || {
+                while written.recv().is_ok() {
+                    let remaining = match state.upgrade() {
+                        Some(state) => {
+                            let status = state.status.lock().unwrap();
+                            match (status.dirty, state.flush) {
+                                // The change was written along with an earlier one
+                                (false, _) => continue,
+                                (true, Flush::Interval(interval)) => {
+                                    interval.saturating_sub(status.flushed.elapsed())
+                                }
+                                (true, Flush::Always | Flush::Manual) => Duration::ZERO,
+                            }
+                        }
+                        None => return,
+                    };
+                    // The share is not kept alive while waiting for the interval to pass
+                    thread::sleep(remaining);
+                    let state = match state.upgrade() {
+                        Some(state) => state,
+                        None => return,
+                    };
+                    let mut status = state.status.lock().unwrap();
+                    if let Err(error) = state.flush(&mut status) {
+                        error!("failed to write `{}`: {error}", state.path.display());
+                    }
+                }
+            })?;
+        Ok(())
+    }
+}
+
+impl<T> Drop for FileState<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn drop(&mut self) {
+        let mut status = self.status.lock().unwrap();
+        if let Err(error) = self.flush(&mut status) {
+            error!("failed to write `{}`: {error}", self.path.display());
+        }
+    }
+}
+
+impl<T> Clone for ShareFile<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn clone(&self) -> Self {
+        ShareFile {
+            id: self.id,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> ShareRead for ShareFile<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    type Value = T;
+    type Read<'a> = ShareGuard<'a, T> where T: 'a;
+
+    fn read<'a>(&'a self) -> Self::Read<'a> {
+        self.state.value.lock().unwrap().into()
+    }
+}
+
+impl<T> ShareWrite for ShareFile<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    type Value = T;
+
+    fn write(&self, value: TaskValue<Self::Value>) {
+        self.modify(|current| *current = value);
+    }
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut TaskValue<Self::Value>),
+    {
+        self.modify(f);
+    }
+}
+
+impl<T> ShareFile<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Changes the value using `f` while holding its lock, see [`ShareWrite::update`].
+    fn modify<F>(&self, f: F)
+    where
+        F: FnOnce(&mut TaskValue<T>),
+    {
+        f(&mut self.state.value.lock().unwrap());
+        self.state.status.lock().unwrap().dirty = true;
+        if let Some(written) = &self.state.written {
+            // Sending only fails once the thread writing the file stopped with the share
+            let _ = written.send(());
+        }
+        notify(self.id);
+    }
+}
+
+impl<T> ShareUpdate for ShareFile<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        is_updated(ids, self.id)
+    }
+}
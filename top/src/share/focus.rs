@@ -0,0 +1,158 @@
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::share::derived::DerivedWrapper;
+use crate::share::{ShareRead, ShareUpdate, ShareWrite};
+use crate::task::TaskValue;
+
+/// Share of a part of the value of another share, created using [`ShareWrite::focus`]. It shares
+/// the id of the share it focuses on, so writing to it updates that share as a whole. Writes change
+/// the other share using [`ShareWrite::update`], so writes through several focuses on the same
+/// share do not undo each other.
+///
+/// Errors and empty values cannot be stored in the value of the other share, so they are kept by
+/// the focus itself until a value is written again, or until the part of the other share it
+/// focuses on changes. The same holds for values written while the other share has no value to
+/// write them to.
+pub struct ShareFocus<S, G, P, T> {
+    share: S,
+    get: Arc<G>,
+    set: Arc<P>,
+    pending: Arc<Mutex<Option<Pending<T>>>>,
+}
+
+/// A value written to a focus that could not be written to the share it focuses on, along with the
+/// part of that share at the time, which it replaces for as long as that part stays the same.
+#[derive(Debug)]
+struct Pending<T> {
+    value: TaskValue<T>,
+    part: TaskValue<T>,
+}
+
+impl<S, G, P, T> ShareFocus<S, G, P, T> {
+    pub(crate) fn new(share: S, get: G, set: P) -> Self {
+        ShareFocus {
+            share,
+            get: Arc::new(get),
+            set: Arc::new(set),
+            pending: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl<S, G, P, T> Clone for ShareFocus<S, G, P, T>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        ShareFocus {
+            share: self.share.clone(),
+            get: self.get.clone(),
+            set: self.set.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<S, G, P, T> Debug for ShareFocus<S, G, P, T>
+where
+    S: Debug,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShareFocus")
+            .field("share", &self.share)
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The part of `whole` that `get` focuses on.
+fn part<V, G, T>(get: &G, whole: &TaskValue<V>) -> TaskValue<T>
+where
+    G: Fn(&V) -> &T,
+    T: Clone,
+{
+    match whole {
+        TaskValue::Stable(value) => TaskValue::Stable(get(value).clone()),
+        TaskValue::Unstable(value) => TaskValue::Unstable(get(value).clone()),
+        TaskValue::Error(error) => TaskValue::Error(error.clone()),
+        TaskValue::Empty => TaskValue::Empty,
+    }
+}
+
+impl<S, G, P, T> ShareRead for ShareFocus<S, G, P, T>
+where
+    S: ShareRead,
+    G: Fn(&S::Value) -> &T,
+    T: Clone + PartialEq,
+{
+    type Value = T;
+    type Read<'a> = DerivedWrapper<T> where S: 'a, G: 'a, P: 'a, T: 'a;
+
+    fn read<'a>(&'a self) -> Self::Read<'a> {
+        let mut pending = self.pending.lock().unwrap();
+        let part = part(&*self.get, self.share.read().as_ref());
+        if let Some(value) = pending.as_ref().filter(|pending| pending.part == part) {
+            return DerivedWrapper(value.value.clone());
+        }
+        // The part changed elsewhere, so the pending value no longer applies
+        *pending = None;
+        DerivedWrapper(part)
+    }
+}
+
+impl<S, G, P, T> ShareWrite for ShareFocus<S, G, P, T>
+where
+    S: ShareRead + ShareWrite<Value = <S as ShareRead>::Value>,
+    <S as ShareRead>::Value: Clone,
+    G: Fn(&<S as ShareRead>::Value) -> &T,
+    P: Fn(&mut <S as ShareRead>::Value, T),
+    T: Clone,
+{
+    type Value = T;
+
+    fn write(&self, value: TaskValue<Self::Value>) {
+        let mut pending = self.pending.lock().unwrap();
+        *pending = None;
+        self.share.update(|whole| {
+            *whole = match (std::mem::take(whole), value) {
+                (TaskValue::Stable(mut whole), TaskValue::Stable(value)) => {
+                    (self.set)(&mut whole, value);
+                    TaskValue::Stable(whole)
+                }
+                (
+                    TaskValue::Stable(mut whole) | TaskValue::Unstable(mut whole),
+                    TaskValue::Stable(value) | TaskValue::Unstable(value),
+                ) => {
+                    (self.set)(&mut whole, value);
+                    TaskValue::Unstable(whole)
+                }
+                (whole, value) => {
+                    *pending = Some(Pending {
+                        value,
+                        part: part(&*self.get, &whole),
+                    });
+                    whole
+                }
+            };
+        });
+    }
+}
+
+impl<S, G, P, T> ShareUpdate for ShareFocus<S, G, P, T>
+where
+    S: ShareUpdate,
+{
+    /// The id of the share this focuses on, which is updated whenever any part of it is.
+    fn id(&self) -> Uuid {
+        self.share.id()
+    }
+
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        self.share.updated(ids)
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::share::vec::ChildrenGuard;
+use crate::share::{is_updated, ShareChildren, ShareCreate, ShareRead, ShareUpdate, ShareWrite};
+use crate::task::{OptionExt, TaskValue};
+
+/// Shares a map of type `M` as a list of entries, where `K` shares the key and `V` shares the value
+/// of each entry. Entries can be changed individually, which also counts as an update of the map.
+#[derive(Debug)]
+pub struct ShareMap<M, K, V> {
+    id: Uuid,
+    shares: Arc<Mutex<Vec<(K, V)>>>,
+    map: PhantomData<fn() -> M>,
+}
+
+impl<M, K, V> Clone for ShareMap<M, K, V> {
+    fn clone(&self) -> Self {
+        ShareMap {
+            id: self.id,
+            shares: self.shares.clone(),
+            map: PhantomData,
+        }
+    }
+}
+
+impl<M, K, V> ShareMap<M, K, V>
+where
+    M: IntoIterator<Item = (K::Value, V::Value)>,
+    K: ShareCreate,
+    V: ShareCreate,
+{
+    pub fn new(value: Option<M>) -> Self {
+        ShareMap::create(value.into_unstable())
+    }
+}
+
+pub struct MapWrapper<M>(TaskValue<M>);
+
+impl<M> AsRef<TaskValue<M>> for MapWrapper<M> {
+    fn as_ref(&self) -> &TaskValue<M> {
+        &self.0
+    }
+}
+
+impl<M, K, V> ShareRead for ShareMap<M, K, V>
+where
+    M: FromIterator<(K::Value, V::Value)>,
+    K: ShareRead,
+    K::Value: Clone + PartialEq,
+    V: ShareRead,
+    V::Value: Clone,
+{
+    type Value = M;
+    type Read<'a> = MapWrapper<M> where M: 'a, K: 'a, V: 'a;
+
+    fn read<'a>(&'a self) -> Self::Read<'a> {
+        let entries: TaskValue<Vec<_>> = self
+            .shares
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, value)| {
+                key.read()
+                    .as_ref()
+                    .clone()
+                    .and(value.read().as_ref().clone())
+            })
+            .collect();
+        let map = match entries {
+            TaskValue::Stable(entries) | TaskValue::Unstable(entries) => {
+                let duplicate = entries.iter().enumerate().any(|(index, (key, _))| {
+                    entries[..index].iter().any(|(other, _)| other == key)
+                });
+                match duplicate {
+                    true => TaskValue::Error("Keys must be unique".to_owned()),
+                    false => TaskValue::Unstable(entries.into_iter().collect()),
+                }
+            }
+            TaskValue::Error(error) => TaskValue::Error(error),
+            TaskValue::Empty => TaskValue::Empty,
+        };
+        MapWrapper(map)
+    }
+}
+
+impl<M, K, V> ShareWrite for ShareMap<M, K, V>
+where
+    M: IntoIterator<Item = (K::Value, V::Value)>,
+    K: ShareCreate,
+    V: ShareCreate,
+{
+    type Value = M;
+
+    fn write(&self, value: TaskValue<Self::Value>) {
+        *self.children() = Self::entries(value);
+    }
+}
+
+impl<M, K, V> ShareCreate for ShareMap<M, K, V>
+where
+    M: IntoIterator<Item = (K::Value, V::Value)>,
+    K: ShareCreate,
+    V: ShareCreate,
+{
+    fn create(value: TaskValue<Self::Value>) -> Self {
+        ShareMap {
+            id: Uuid::new_v4(),
+            shares: Arc::new(Mutex::new(Self::entries(value))),
+            map: PhantomData,
+        }
+    }
+}
+
+impl<M, K, V> ShareMap<M, K, V>
+where
+    M: IntoIterator<Item = (K::Value, V::Value)>,
+    K: ShareCreate,
+    V: ShareCreate,
+{
+    fn entries(value: TaskValue<M>) -> Vec<(K, V)> {
+        match value {
+            TaskValue::Stable(value) => value
+                .into_iter()
+                .map(|(key, value)| {
+                    (
+                        K::create(TaskValue::Stable(key)),
+                        V::create(TaskValue::Stable(value)),
+                    )
+                })
+                .collect(),
+            TaskValue::Unstable(value) => value
+                .into_iter()
+                .map(|(key, value)| {
+                    (
+                        K::create(TaskValue::Unstable(key)),
+                        V::create(TaskValue::Unstable(value)),
+                    )
+                })
+                .collect(),
+            TaskValue::Error(error) => vec![(
+                K::create(TaskValue::Error(error.clone())),
+                V::create(TaskValue::Error(error)),
+            )],
+            TaskValue::Empty => vec![(K::create(TaskValue::Empty), V::create(TaskValue::Empty))],
+        }
+    }
+}
+
+impl<M, K, V> ShareUpdate for ShareMap<M, K, V>
+where
+    K: ShareUpdate,
+    V: ShareUpdate,
+{
+    /// The id of the map itself, which is only updated when entries are added or removed.
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        is_updated(ids, self.id)
+            || self
+                .shares
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(key, value)| key.updated(ids) || value.updated(ids))
+    }
+}
+
+impl<M, K, V> ShareChildren for ShareMap<M, K, V> {
+    type Child = (K, V);
+    type Children<'a> = ChildrenGuard<'a, (K, V)> where M: 'a, K: 'a, V: 'a;
+
+    fn children<'a>(&'a self) -> Self::Children<'a> {
+        ChildrenGuard::new(self.id, self.shares.lock().unwrap())
+    }
+}
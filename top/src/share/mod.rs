@@ -1,12 +1,26 @@
 use std::collections::BTreeSet;
-use std::sync::MutexGuard;
+use std::ops::DerefMut;
 
 use uuid::Uuid;
 
 use crate::task::TaskValue;
+pub use derived::{ShareMapped, ShareZip};
+pub use file::{Flush, ShareFile};
+pub use focus::ShareFocus;
+pub use map::ShareMap;
+pub use notify::{is_updated, notify, subscribe, ALL};
+#[cfg(feature = "sqlite")]
+pub use sqlite::{Database, ShareRow, ShareTable};
 pub use value::ShareValue;
 pub use vec::ShareVec;
 
+mod derived;
+mod file;
+mod focus;
+mod map;
+mod notify;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod value;
 mod vec;
 
@@ -17,14 +31,65 @@ pub trait ShareRead {
         Self: 'a;
 
     fn read<'a>(&'a self) -> Self::Read<'a>;
+
+    /// Computes a new share from this one, whose value is `f` applied to the value of this share.
+    fn map<F, T>(self, f: F) -> ShareMapped<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Self::Value) -> T,
+    {
+        ShareMapped::new(self, f)
+    }
+
+    /// Combines this share with `other` into a share of both their values.
+    fn zip<S>(self, other: S) -> ShareZip<Self, S>
+    where
+        Self: Sized,
+        S: ShareRead,
+    {
+        ShareZip::new(self, other)
+    }
 }
 
 pub trait ShareWrite {
     type Value;
 
-    fn create(value: TaskValue<Self::Value>) -> Self;
-
     fn write(&self, value: TaskValue<Self::Value>);
+
+    /// Changes the value of this share in place using `f`. Shares that keep their value behind a
+    /// single lock do so while holding it, such that no other write can happen in between. By
+    /// default, the value is read, changed and written back as separate steps.
+    fn update<F>(&self, f: F)
+    where
+        Self: ShareRead<Value = <Self as ShareWrite>::Value>,
+        <Self as ShareWrite>::Value: Clone,
+        F: FnOnce(&mut TaskValue<<Self as ShareWrite>::Value>),
+    {
+        let mut value = self.read().as_ref().clone();
+        f(&mut value);
+        self.write(value);
+    }
+
+    /// Focuses on a part of the value of this share, such as a field of a struct, which `get`
+    /// borrows and `set` replaces. Writing to the resulting share writes the whole value of this
+    /// share.
+    fn focus<G, P, T>(self, get: G, set: P) -> ShareFocus<Self, G, P, T>
+    where
+        Self: ShareRead<Value = <Self as ShareWrite>::Value> + Sized,
+        G: Fn(&<Self as ShareWrite>::Value) -> &T,
+        P: Fn(&mut <Self as ShareWrite>::Value, T),
+    {
+        ShareFocus::new(self, get, set)
+    }
+}
+
+/// Shares that can be created on their own, such as new elements of a list.
+///
+/// This is separate from [`ShareWrite`], as not every share that can be written can be created from
+/// just a value: a [`ShareFocus`] needs the share it focuses on, a [`ShareFile`] needs its path and
+/// a `ShareTable` needs its database.
+pub trait ShareCreate: ShareWrite {
+    fn create(value: TaskValue<Self::Value>) -> Self;
 }
 
 pub trait ShareUpdate {
@@ -35,6 +100,10 @@ pub trait ShareUpdate {
 
 pub trait ShareChildren {
     type Child;
+    /// Gives access to the children, where changes to them are stored once it is dropped.
+    type Children<'a>: DerefMut<Target = Vec<Self::Child>>
+    where
+        Self: 'a;
 
-    fn children(&self) -> MutexGuard<Vec<Self::Child>>;
+    fn children<'a>(&'a self) -> Self::Children<'a>;
 }
@@ -0,0 +1,37 @@
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// The number of updates kept for subscribers that have not received them yet. Subscribers that
+/// fall further behind miss updates, and have to refresh everything instead.
+const CAPACITY: usize = 1024;
+
+static UPDATES: OnceLock<broadcast::Sender<Uuid>> = OnceLock::new();
+
+fn updates() -> &'static broadcast::Sender<Uuid> {
+    UPDATES.get_or_init(|| broadcast::channel(CAPACITY).0)
+}
+
+/// Notifies all subscribers that the share with `id` was updated. Shares do this whenever they are
+/// written or their children are changed.
+pub fn notify(id: Uuid) {
+    // Sending only fails if there are no subscribers, in which case nobody needs to know
+    let _ = updates().send(id);
+}
+
+/// Subscribes to the ids of the shares that are updated from now on.
+pub fn subscribe() -> broadcast::Receiver<Uuid> {
+    updates().subscribe()
+}
+
+/// Stands for the ids of all shares, for subscribers that missed updates and no longer know which
+/// shares were updated.
+pub const ALL: Uuid = Uuid::max();
+
+/// Whether the share with `id` is among the updated `ids`, which all shares are if they contain
+/// [`ALL`].
+pub fn is_updated(ids: &BTreeSet<Uuid>, id: Uuid) -> bool {
+    ids.contains(&id) || ids.contains(&ALL)
+}
@@ -0,0 +1,527 @@
+use std::collections::BTreeSet;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use log::error;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::share::derived::DerivedWrapper;
+use crate::share::value::ShareGuard;
+use crate::share::{
+    is_updated, notify, ShareChildren, ShareCreate, ShareRead, ShareUpdate, ShareWrite,
+};
+use crate::task::{OptionExt, TaskValue};
+
+/// SQLite database in which shares are stored by name. Values are stored as JSON, and shares keep
+/// their ids when the database is opened again, so they are still refreshed after a restart.
+///
+/// Empty values are stored as `NULL`. Errors are not stored, so the database keeps the last value
+/// that was written before.
+#[derive(Clone, Debug)]
+pub struct Database {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl Database {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Database::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Database::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> rusqlite::Result<Self> {
+        connection.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS top_share (name TEXT PRIMARY KEY, id BLOB NOT NULL);
+            CREATE TABLE IF NOT EXISTS top_row (
+                id BLOB PRIMARY KEY,
+                share BLOB NOT NULL,
+                position INTEGER NOT NULL,
+                value TEXT
+            );
+            CREATE INDEX IF NOT EXISTS top_row_share ON top_row (share, position);
+            ",
+        )?;
+        Ok(Database {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Opens the single value stored as `name`, which starts out with `value` if it is not stored
+    /// yet.
+    pub fn row<T>(&self, name: &str, value: Option<T>) -> rusqlite::Result<ShareRow<T>>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        let id = match share_id(&transaction, name)? {
+            Some(id) => id,
+            None => {
+                let id = Uuid::new_v4();
+                transaction.execute(
+                    "INSERT INTO top_share (name, id) VALUES (?1, ?2)",
+                    params![name, id],
+                )?;
+                transaction.execute(
+                    "INSERT INTO top_row (id, share, position, value) VALUES (?1, ?1, 0, ?2)",
+                    params![id, to_json(&value.into_unstable())?],
+                )?;
+                id
+            }
+        };
+        let value = transaction.query_row(
+            "SELECT value FROM top_row WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        let value = from_json(value)?;
+        transaction.commit()?;
+        Ok(ShareRow::stored(id, value, self.clone()))
+    }
+
+    /// Opens the list stored as `name`, which starts out empty if it is not stored yet.
+    pub fn table<T>(&self, name: &str) -> rusqlite::Result<ShareTable<T>>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        let id = match share_id(&transaction, name)? {
+            Some(id) => id,
+            None => {
+                let id = Uuid::new_v4();
+                transaction.execute(
+                    "INSERT INTO top_share (name, id) VALUES (?1, ?2)",
+                    params![name, id],
+                )?;
+                id
+            }
+        };
+        let rows = transaction
+            .prepare("SELECT id, value FROM top_row WHERE share = ?1 ORDER BY position")?
+            .query_map(params![id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .map(|row| {
+                let (row_id, value) = row?;
+                Ok(ShareRow::stored(row_id, from_json(value)?, self.clone()))
+            })
+            .collect::<rusqlite::Result<_>>()?;
+        transaction.commit()?;
+        Ok(ShareTable {
+            id,
+            database: self.clone(),
+            rows: Arc::new(Mutex::new(rows)),
+        })
+    }
+
+    /// Runs `f` in a single transaction, which is only committed if `f` succeeds.
+    fn transaction<F, R>(&self, f: F) -> rusqlite::Result<R>
+    where
+        F: FnOnce(&Transaction) -> rusqlite::Result<R>,
+    {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        let result = f(&transaction)?;
+        transaction.commit()?;
+        Ok(result)
+    }
+}
+
+fn share_id(transaction: &Transaction, name: &str) -> rusqlite::Result<Option<Uuid>> {
+    transaction
+        .query_row(
+            "SELECT id FROM top_share WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()
+}
+
+/// Stores the value of the row with `id`, unless it is an error.
+fn update_row<T>(transaction: &Transaction, id: Uuid, value: &TaskValue<T>) -> rusqlite::Result<()>
+where
+    T: Serialize,
+{
+    if let TaskValue::Error(_) = value {
+        return Ok(());
+    }
+    transaction.execute(
+        "UPDATE top_row SET value = ?2 WHERE id = ?1",
+        params![id, to_json(value)?],
+    )?;
+    Ok(())
+}
+
+/// Stores `rows` as the rows of the table with `id`. Rows that are no longer in the table are
+/// deleted, and rows that are not stored yet are inserted.
+fn store_rows<T>(transaction: &Transaction, id: Uuid, rows: &[ShareRow<T>]) -> rusqlite::Result<()>
+where
+    T: Serialize,
+{
+    let ids: BTreeSet<Uuid> = rows.iter().map(|row| row.id).collect();
+    let stored: Vec<Uuid> = transaction
+        .prepare("SELECT id FROM top_row WHERE share = ?1")?
+        .query_map(params![id], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    for row_id in stored.iter().filter(|row_id| !ids.contains(row_id)) {
+        transaction.execute("DELETE FROM top_row WHERE id = ?1", params![row_id])?;
+    }
+    for (position, row) in rows.iter().enumerate() {
+        match row.database.get() {
+            Some(_) => transaction.execute(
+                "UPDATE top_row SET position = ?2 WHERE id = ?1",
+                params![row.id, position],
+            )?,
+            None => transaction.execute(
+                "INSERT INTO top_row (id, share, position, value) VALUES (?1, ?2, ?3, ?4)",
+                params![row.id, id, position, to_json(&row.value.lock().unwrap())?],
+            )?,
+        };
+    }
+    Ok(())
+}
+
+fn to_json<T>(value: &TaskValue<T>) -> rusqlite::Result<Option<String>>
+where
+    T: Serialize,
+{
+    match value {
+        TaskValue::Stable(value) | TaskValue::Unstable(value) => serde_json::to_string(value)
+            .map(Some)
+            .map_err(|error| rusqlite::Error::ToSqlConversionFailure(Box::new(error))),
+        TaskValue::Error(_) | TaskValue::Empty => Ok(None),
+    }
+}
+
+/// Whether writing `new` over `current` changes it, where values are compared by their JSON.
+fn changes<T>(current: &TaskValue<T>, new: &TaskValue<T>) -> bool
+where
+    T: Serialize,
+{
+    match (current, new) {
+        (TaskValue::Stable(current), TaskValue::Stable(new))
+        | (TaskValue::Unstable(current), TaskValue::Unstable(new)) => {
+            match (serde_json::to_string(current), serde_json::to_string(new)) {
+                (Ok(current), Ok(new)) => current != new,
+                _ => true,
+            }
+        }
+        (TaskValue::Error(current), TaskValue::Error(new)) => current != new,
+        (TaskValue::Empty, TaskValue::Empty) => false,
+        _ => true,
+    }
+}
+
+fn from_json<T>(value: Option<String>) -> rusqlite::Result<TaskValue<T>>
+where
+    T: DeserializeOwned,
+{
+    match value {
+        Some(value) => serde_json::from_str(&value)
+            .map(TaskValue::Unstable)
+            .map_err(|error| {
+                rusqlite::Error::FromSqlConversionFailure(0, Type::Text, Box::new(error))
+            }),
+        None => Ok(TaskValue::Empty),
+    }
+}
+
+/// Shares a value stored in a row of a [`Database`], either on its own or as an element of a
+/// [`ShareTable`]. Rows created using [`ShareCreate::create`] are stored once they are added to a
+/// table.
+#[derive(Debug)]
+pub struct ShareRow<T> {
+    id: Uuid,
+    value: Arc<Mutex<TaskValue<T>>>,
+    database: Arc<OnceLock<Database>>,
+}
+
+impl<T> ShareRow<T> {
+    fn stored(id: Uuid, value: TaskValue<T>, database: Database) -> Self {
+        ShareRow {
+            id,
+            value: Arc::new(Mutex::new(value)),
+            database: Arc::new(OnceLock::from(database)),
+        }
+    }
+}
+
+impl<T> Clone for ShareRow<T> {
+    fn clone(&self) -> Self {
+        ShareRow {
+            id: self.id,
+            value: self.value.clone(),
+            database: self.database.clone(),
+        }
+    }
+}
+
+impl<T> ShareRead for ShareRow<T> {
+    type Value = T;
+    type Read<'a> = ShareGuard<'a, T> where T: 'a;
+
+    fn read<'a>(&'a self) -> Self::Read<'a> {
+        self.value.lock().unwrap().into()
+    }
+}
+
+impl<T> ShareWrite for ShareRow<T>
+where
+    T: Serialize,
+{
+    type Value = T;
+
+    fn write(&self, value: TaskValue<Self::Value>) {
+        self.replace(&mut self.value.lock().unwrap(), value);
+    }
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut TaskValue<Self::Value>),
+        T: Clone,
+    {
+        let mut current = self.value.lock().unwrap();
+        let mut value = current.clone();
+        f(&mut value);
+        self.replace(&mut current, value);
+    }
+}
+
+impl<T> ShareRow<T>
+where
+    T: Serialize,
+{
+    /// Replaces `current`, the locked value of this row, with `value` once it is stored. If storing
+    /// it fails, the row keeps its current value.
+    fn replace(&self, current: &mut TaskValue<T>, value: TaskValue<T>) {
+        if let Some(database) = self.database.get() {
+            let stored = database.transaction(|transaction| update_row(transaction, self.id, &value));
+            if let Err(error) = stored {
+                error!("failed to store row {}: {error}", self.id);
+                return;
+            }
+        }
+        *current = value;
+        notify(self.id);
+    }
+}
+
+impl<T> ShareCreate for ShareRow<T>
+where
+    T: Serialize,
+{
+    fn create(value: TaskValue<Self::Value>) -> Self {
+        ShareRow {
+            id: Uuid::new_v4(),
+            value: Arc::new(Mutex::new(value)),
+            database: Arc::new(OnceLock::new()),
+        }
+    }
+}
+
+impl<T> ShareUpdate for ShareRow<T> {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        is_updated(ids, self.id)
+    }
+}
+
+/// Shares a list stored in a [`Database`], with a [`ShareRow`] for each of its elements. Adding,
+/// removing and moving elements is stored in a single transaction. Writing the whole list updates
+/// the existing rows in place, so they keep their ids.
+#[derive(Debug)]
+pub struct ShareTable<T> {
+    id: Uuid,
+    database: Database,
+    rows: Arc<Mutex<Vec<ShareRow<T>>>>,
+}
+
+impl<T> Clone for ShareTable<T> {
+    fn clone(&self) -> Self {
+        ShareTable {
+            id: self.id,
+            database: self.database.clone(),
+            rows: self.rows.clone(),
+        }
+    }
+}
+
+impl<T> ShareRead for ShareTable<T>
+where
+    T: Clone,
+{
+    type Value = Vec<T>;
+    type Read<'a> = DerivedWrapper<Vec<T>> where T: 'a;
+
+    fn read<'a>(&'a self) -> Self::Read<'a> {
+        let vec = self
+            .rows
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|row| row.read().as_ref().clone())
+            .collect();
+        DerivedWrapper(vec)
+    }
+}
+
+impl<T> ShareWrite for ShareTable<T>
+where
+    T: Serialize,
+{
+    type Value = Vec<T>;
+
+    /// Writes each element to the row at the same position, keeping the ids of the rows. Rows
+    /// whose value does not change are left alone, and rows are only inserted or deleted if the
+    /// length of the list changes. All of this is stored in a single transaction, and the list only
+    /// changes once it is committed.
+    fn write(&self, value: TaskValue<Self::Value>) {
+        let values: Vec<_> = match value {
+            TaskValue::Stable(value) => value.into_iter().map(TaskValue::Stable).collect(),
+            TaskValue::Unstable(value) => value.into_iter().map(TaskValue::Unstable).collect(),
+            TaskValue::Error(error) => vec![TaskValue::Error(error)],
+            TaskValue::Empty => vec![TaskValue::Empty],
+        };
+        let length = values.len();
+        let mut rows = self.rows.lock().unwrap();
+        // The rows stay locked until they are written, so they are not written elsewhere meanwhile
+        let mut current: Vec<_> = rows.iter().map(|row| row.value.lock().unwrap()).collect();
+        let mut values = values.into_iter();
+        let mut changed = Vec::new();
+        for (index, current) in current.iter().enumerate() {
+            match values.next() {
+                Some(value) => {
+                    if changes(current, &value) {
+                        changed.push((index, value));
+                    }
+                }
+                None => break,
+            }
+        }
+        let resized = (length != rows.len()).then(|| {
+            let mut resized: Vec<_> = rows.iter().take(length).cloned().collect();
+            resized.extend(values.map(ShareRow::create));
+            resized
+        });
+
+        let stored = self.database.transaction(|transaction| {
+            for (index, value) in &changed {
+                update_row(transaction, rows[*index].id, value)?;
+            }
+            match &resized {
+                Some(resized) => store_rows(transaction, self.id, resized),
+                None => Ok(()),
+            }
+        });
+        if let Err(error) = stored {
+            error!("failed to store table {}: {error}", self.id);
+            return;
+        }
+
+        for (index, value) in changed {
+            *current[index] = value;
+            notify(rows[index].id);
+        }
+        drop(current);
+        if let Some(resized) = resized {
+            for row in &resized {
+                let _ = row.database.set(self.database.clone());
+            }
+            *rows = resized;
+            notify(self.id);
+        }
+    }
+}
+
+impl<T> ShareUpdate for ShareTable<T> {
+    /// The id of the list itself, which is only updated when elements are added, removed or moved,
+    /// or when the whole list is written.
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        is_updated(ids, self.id) || self.rows.lock().unwrap().iter().any(|row| row.updated(ids))
+    }
+}
+
+impl<T> ShareChildren for ShareTable<T>
+where
+    T: Serialize,
+{
+    type Child = ShareRow<T>;
+    type Children<'a> = TableGuard<'a, T> where T: 'a;
+
+    fn children<'a>(&'a self) -> Self::Children<'a> {
+        TableGuard {
+            table: self,
+            rows: self.rows.lock().unwrap(),
+            changed: false,
+        }
+    }
+}
+
+/// Gives access to the rows of a [`ShareTable`], and stores them once it is dropped if they were
+/// changed.
+pub struct TableGuard<'a, T>
+where
+    T: Serialize,
+{
+    table: &'a ShareTable<T>,
+    rows: MutexGuard<'a, Vec<ShareRow<T>>>,
+    changed: bool,
+}
+
+impl<'a, T> Deref for TableGuard<'a, T>
+where
+    T: Serialize,
+{
+    type Target = Vec<ShareRow<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rows
+    }
+}
+
+impl<'a, T> DerefMut for TableGuard<'a, T>
+where
+    T: Serialize,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.changed = true;
+        &mut self.rows
+    }
+}
+
+impl<'a, T> Drop for TableGuard<'a, T>
+where
+    T: Serialize,
+{
+    fn drop(&mut self) {
+        if self.changed {
+            let stored = self
+                .table
+                .database
+                .transaction(|transaction| store_rows(transaction, self.table.id, &self.rows));
+            match stored {
+                Ok(()) => {
+                    for row in self.rows.iter() {
+                        let _ = row.database.set(self.table.database.clone());
+                    }
+                }
+                Err(error) => error!("failed to store table {}: {error}", self.table.id),
+            }
+            notify(self.table.id);
+        }
+    }
+}
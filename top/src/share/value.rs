@@ -4,7 +4,7 @@ use std::sync::{Arc, Mutex, MutexGuard};
 
 use uuid::Uuid;
 
-use crate::share::{ShareRead, ShareUpdate, ShareWrite};
+use crate::share::{is_updated, notify, ShareCreate, ShareRead, ShareUpdate, ShareWrite};
 use crate::task::{OptionExt, TaskValue};
 
 #[derive(Clone, Debug)]
@@ -48,12 +48,23 @@ impl<T> ShareRead for ShareValue<T> {
 impl<T> ShareWrite for ShareValue<T> {
     type Value = T;
 
-    fn create(value: TaskValue<Self::Value>) -> Self {
-        ShareValue::new(value.into())
-    }
-
     fn write(&self, value: TaskValue<Self::Value>) {
         *self.value.lock().unwrap() = value;
+        notify(self.id);
+    }
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut TaskValue<Self::Value>),
+    {
+        f(&mut self.value.lock().unwrap());
+        notify(self.id);
+    }
+}
+
+impl<T> ShareCreate for ShareValue<T> {
+    fn create(value: TaskValue<Self::Value>) -> Self {
+        ShareValue::new(value.into())
     }
 }
 
@@ -63,6 +74,6 @@ impl<T> ShareUpdate for ShareValue<T> {
     }
 
     fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
-        ids.contains(&self.id)
+        is_updated(ids, self.id)
     }
 }
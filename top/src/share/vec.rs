@@ -1,19 +1,25 @@
 use std::collections::BTreeSet;
+use std::ops::{Deref, DerefMut};
 use std::sync::{Arc, Mutex, MutexGuard};
 
 use uuid::Uuid;
 
-use crate::share::{ShareChildren, ShareRead, ShareUpdate, ShareWrite};
+use crate::share::{
+    is_updated, notify, ShareChildren, ShareCreate, ShareRead, ShareUpdate, ShareWrite,
+};
 use crate::task::{OptionExt, TaskValue};
 
+/// Shares a list, where `S` shares each of its elements. Elements can be changed individually,
+/// which also counts as an update of the list.
 #[derive(Clone, Debug)]
 pub struct ShareVec<S> {
+    id: Uuid,
     shares: Arc<Mutex<Vec<S>>>,
 }
 
 impl<S> ShareVec<S>
 where
-    S: ShareWrite,
+    S: ShareCreate,
 {
     pub fn new(value: Option<Vec<S::Value>>) -> Self {
         ShareVec::create(value.into_unstable())
@@ -50,12 +56,12 @@ where
 
 impl<S> ShareWrite for ShareVec<S>
 where
-    S: ShareWrite,
+    S: ShareCreate,
 {
     type Value = Vec<S::Value>;
 
-    fn create(value: TaskValue<Self::Value>) -> Self {
-        let shares = match value {
+    fn write(&self, value: TaskValue<Self::Value>) {
+        *self.children() = match value {
             TaskValue::Stable(value) => value
                 .into_iter()
                 .map(|value| S::create(TaskValue::Stable(value)))
@@ -67,13 +73,15 @@ where
             TaskValue::Error(error) => vec![S::create(TaskValue::Error(error))],
             TaskValue::Empty => vec![S::create(TaskValue::Empty)],
         };
-        ShareVec {
-            shares: Arc::new(Mutex::new(shares)),
-        }
     }
+}
 
-    fn write(&self, value: TaskValue<Self::Value>) {
-        *self.shares.lock().unwrap() = match value {
+impl<S> ShareCreate for ShareVec<S>
+where
+    S: ShareCreate,
+{
+    fn create(value: TaskValue<Self::Value>) -> Self {
+        let shares = match value {
             TaskValue::Stable(value) => value
                 .into_iter()
                 .map(|value| S::create(TaskValue::Stable(value)))
@@ -85,6 +93,10 @@ where
             TaskValue::Error(error) => vec![S::create(TaskValue::Error(error))],
             TaskValue::Empty => vec![S::create(TaskValue::Empty)],
         };
+        ShareVec {
+            id: Uuid::new_v4(),
+            shares: Arc::new(Mutex::new(shares)),
+        }
     }
 }
 
@@ -92,20 +104,69 @@ impl<S> ShareUpdate for ShareVec<S>
 where
     S: ShareUpdate,
 {
+    /// The id of the list itself, which is only updated when elements are added, removed or moved,
+    /// or when the whole list is written.
     fn id(&self) -> Uuid {
-        todo!()
+        self.id
     }
 
-    fn updated(&self, _ids: &BTreeSet<Uuid>) -> bool {
-        // self.shares.iter().any(|share| share.updated(ids))
-        true
+    fn updated(&self, ids: &BTreeSet<Uuid>) -> bool {
+        is_updated(ids, self.id)
+            || self
+                .shares
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|share| share.updated(ids))
     }
 }
 
 impl<S> ShareChildren for ShareVec<S> {
     type Child = S;
+    type Children<'a> = ChildrenGuard<'a, S> where S: 'a;
+
+    fn children<'a>(&'a self) -> Self::Children<'a> {
+        ChildrenGuard::new(self.id, self.shares.lock().unwrap())
+    }
+}
+
+/// Gives access to the children of a share, and notifies that the share was updated once it is
+/// dropped if they were changed.
+pub struct ChildrenGuard<'a, C> {
+    id: Uuid,
+    children: MutexGuard<'a, Vec<C>>,
+    changed: bool,
+}
+
+impl<'a, C> ChildrenGuard<'a, C> {
+    pub(crate) fn new(id: Uuid, children: MutexGuard<'a, Vec<C>>) -> Self {
+        ChildrenGuard {
+            id,
+            children,
+            changed: false,
+        }
+    }
+}
 
-    fn children(&self) -> MutexGuard<Vec<Self::Child>> {
-        self.shares.lock().unwrap()
+impl<'a, C> Deref for ChildrenGuard<'a, C> {
+    type Target = Vec<C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.children
+    }
+}
+
+impl<'a, C> DerefMut for ChildrenGuard<'a, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.changed = true;
+        &mut self.children
+    }
+}
+
+impl<'a, C> Drop for ChildrenGuard<'a, C> {
+    fn drop(&mut self) {
+        if self.changed {
+            notify(self.id);
+        }
     }
 }
@@ -0,0 +1,508 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareRead, ShareUpdate, ShareValue};
+use crate::task::{OptionExt, TaskValue, Value, WithLabel};
+
+/// Lets the user choose one of `options`.
+#[inline]
+pub fn choose<T>(options: Vec<T>) -> Choose<ShareValue<Vec<T>>, T>
+where
+    T: Clone,
+{
+    Choose::new(ShareValue::new(Some(options)))
+}
+
+/// Lets the user choose one of the options in `share`, which are updated when the share is.
+#[inline]
+pub fn choose_shared<S, T>(share: S) -> Choose<S, T>
+where
+    S: ShareRead<Value = Vec<T>>,
+    T: Clone,
+{
+    Choose::new(share)
+}
+
+/// Lets the user choose any number of `options`.
+#[inline]
+pub fn choose_many<T>(options: Vec<T>) -> ChooseMany<ShareValue<Vec<T>>, T>
+where
+    T: Clone,
+{
+    ChooseMany::new(ShareValue::new(Some(options)))
+}
+
+/// Lets the user choose any number of the options in `share`, which are updated when the share is.
+#[inline]
+pub fn choose_many_shared<S, T>(share: S) -> ChooseMany<S, T>
+where
+    S: ShareRead<Value = Vec<T>>,
+    T: Clone,
+{
+    ChooseMany::new(share)
+}
+
+/// Task for choosing a single option from a list, shown as a dropdown or as radio buttons. Options
+/// are shown using their [`Display`] implementation, and the value of the task is the chosen
+/// option.
+///
+/// Each option that is shown gets its own id, so choosing an option that was removed from the list
+/// in the meantime is rejected instead of choosing whichever option took its place.
+#[derive(Clone, Debug)]
+pub struct Choose<S, T> {
+    id: Uuid,
+    input_id: Uuid,
+    share: S,
+    options: Vec<(Uuid, T)>,
+    chosen: Option<T>,
+    radio: bool,
+    label: Option<String>,
+}
+
+impl<S, T> Choose<S, T>
+where
+    S: ShareRead<Value = Vec<T>>,
+{
+    pub fn new(share: S) -> Self
+    where
+        T: Clone,
+    {
+        let options = options_with_ids(&share);
+        Choose {
+            id: Uuid::new_v4(),
+            input_id: Uuid::new_v4(),
+            share,
+            options,
+            chosen: None,
+            radio: false,
+            label: None,
+        }
+    }
+
+    /// Shows the options as radio buttons instead of a dropdown.
+    pub fn with_radio(mut self) -> Self {
+        self.radio = true;
+        self
+    }
+
+    /// Takes over the current options of the share, forgetting the chosen one if it is no longer
+    /// among them.
+    fn reload(&mut self)
+    where
+        T: Clone + PartialEq,
+    {
+        if let TaskValue::Stable(options) | TaskValue::Unstable(options) =
+            self.share.read().as_ref()
+        {
+            if !matches!(&self.chosen, Some(chosen) if options.contains(chosen)) {
+                self.chosen = None;
+            }
+        }
+        self.options = options_with_ids(&self.share);
+    }
+}
+
+impl<S, T> WithLabel for Choose<S, T> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S, T> Value for Choose<S, T>
+where
+    S: Send + Sync,
+    T: Clone + Send + Sync,
+{
+    type Output = T;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        self.chosen.clone().into_unstable()
+    }
+}
+
+#[async_trait]
+impl<S, T> Handler for Choose<S, T>
+where
+    S: ShareRead<Value = Vec<T>> + Send + Sync,
+    T: Clone + PartialEq + Display + Send + Sync,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        match event {
+            Event::Update { id, value }
+                if id == self.input_id
+                    || self.options.iter().any(|(option_id, _)| *option_id == id) =>
+            {
+                // The value of a dropdown or radio button is the id of the option
+                let option = value.parse().ok().and_then(|option_id: Uuid| {
+                    self.options
+                        .iter()
+                        .find(|(id, _)| *id == option_id)
+                        .map(|(_, option)| option.clone())
+                });
+                match option {
+                    Some(option) if offers(&self.share, &option) => {
+                        self.chosen = Some(option);
+                        Feedback::from(Change::Valid { id })
+                    }
+                    Some(_) => {
+                        // The options changed since they were shown
+                        self.reload();
+                        Feedback::from(Change::Invalid { id }).combined_with(Feedback::from(
+                            Change::Replace {
+                                id: self.id,
+                                html: self.to_html().await,
+                            },
+                        ))
+                    }
+                    None => Feedback::from(Change::Invalid { id }),
+                }
+            }
+            _ => Feedback::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> Refresh for Choose<S, T>
+where
+    S: ShareRead<Value = Vec<T>> + ShareUpdate + Send + Sync,
+    T: Clone + PartialEq + Display + Send + Sync,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        if self.share.updated(ids) {
+            self.reload();
+            Feedback::from(Change::Replace {
+                id: self.id,
+                html: self.to_html().await,
+            })
+        } else {
+            Feedback::new()
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> ToHtml for Choose<S, T>
+where
+    S: ShareRead<Value = Vec<T>> + Send + Sync,
+    T: PartialEq + Display + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+
+        let options = self.options.iter().map(|(id, option)| {
+            let chosen = self.chosen.as_ref() == Some(option);
+            (id, option, chosen)
+        });
+
+        let control: Html = match self.radio {
+            true => options
+                .map(|(id, option, chosen)| {
+                    let checked = if chosen { "checked" } else { "" };
+                    Html(format!(
+                        r#"
+                        <label class="radio">
+                            <input id="{id}" type="radio" name="{}" value="{id}" onchange="update(this)" {checked}/>
+                            {option}
+                        </label>
+                    "#,
+                        self.input_id
+                    ))
+                })
+                .collect(),
+            false => {
+                let placeholder = match self.chosen {
+                    None => r#"<option value="" disabled selected hidden></option>"#,
+                    Some(_) => "",
+                };
+                let options: String = options
+                    .map(|(id, option, chosen)| {
+                        let selected = if chosen { "selected" } else { "" };
+                        format!(r#"<option value="{id}" {selected}>{option}</option>"#)
+                    })
+                    .collect();
+                Html(format!(
+                    r#"
+                    <div class="select">
+                        <select id="{}" onchange="update(this)">{placeholder}{options}</select>
+                    </div>
+                "#,
+                    self.input_id
+                ))
+            }
+        };
+
+        Html(format!(
+            r#"<div id="{}" class="control">{label}{control}</div>"#,
+            self.id
+        ))
+    }
+}
+
+/// Task for choosing any number of options from a list, shown as checkboxes. Options are shown
+/// using their [`Display`] implementation, and the value of the task is the list of chosen options,
+/// in the order they are offered in.
+///
+/// Like with [`Choose`], each option that is shown gets its own id, so toggling an option that was
+/// removed from the list in the meantime is rejected.
+#[derive(Clone, Debug)]
+pub struct ChooseMany<S, T> {
+    id: Uuid,
+    message_id: Uuid,
+    share: S,
+    options: Vec<(Uuid, T)>,
+    chosen: Vec<T>,
+    min: Option<usize>,
+    max: Option<usize>,
+    label: Option<String>,
+}
+
+impl<S, T> ChooseMany<S, T>
+where
+    S: ShareRead<Value = Vec<T>>,
+{
+    pub fn new(share: S) -> Self
+    where
+        T: Clone,
+    {
+        let options = options_with_ids(&share);
+        ChooseMany {
+            id: Uuid::new_v4(),
+            message_id: Uuid::new_v4(),
+            share,
+            options,
+            chosen: Vec::new(),
+            min: None,
+            max: None,
+            label: None,
+        }
+    }
+
+    /// Requires at least `min` options to be chosen.
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Allows at most `max` options to be chosen.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// The message explaining why the number of chosen options is not allowed, if it is not.
+    fn check(&self) -> Option<String> {
+        let count = self.chosen.len();
+        match (self.min, self.max) {
+            (Some(min), _) if count < min => Some(format!("Choose at least {}", options(min))),
+            (_, Some(max)) if count > max => Some(format!("Choose at most {}", options(max))),
+            _ => None,
+        }
+    }
+
+    /// Takes over the current options of the share, forgetting the chosen ones that are no longer
+    /// among them.
+    fn reload(&mut self)
+    where
+        T: Clone + PartialEq,
+    {
+        if let TaskValue::Stable(options) | TaskValue::Unstable(options) =
+            self.share.read().as_ref()
+        {
+            self.chosen.retain(|chosen| options.contains(chosen));
+        }
+        self.options = options_with_ids(&self.share);
+    }
+}
+
+impl<S, T> WithLabel for ChooseMany<S, T> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S, T> Value for ChooseMany<S, T>
+where
+    S: ShareRead<Value = Vec<T>> + Send + Sync,
+    T: Clone + Send + Sync,
+{
+    type Output = Vec<T>;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        match self.check() {
+            None => TaskValue::Unstable(self.chosen.clone()),
+            Some(message) => TaskValue::Error(message),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> Handler for ChooseMany<S, T>
+where
+    S: ShareRead<Value = Vec<T>> + Send + Sync,
+    T: Clone + PartialEq + Display + Send + Sync,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        let toggled = match &event {
+            Event::Update { id, .. } => self
+                .options
+                .iter()
+                .find(|(option_id, _)| option_id == id)
+                .map(|(_, option)| option.clone()),
+            _ => None,
+        };
+        match (event, toggled) {
+            (Event::Update { id, value }, Some(toggled)) => {
+                let checked: bool = match value.parse() {
+                    Ok(checked) => checked,
+                    Err(_) => return Feedback::from(Change::Invalid { id }),
+                };
+                if !offers(&self.share, &toggled) {
+                    // The options changed since they were shown
+                    self.reload();
+                    return Feedback::from(Change::Invalid { id }).combined_with(Feedback::from(
+                        Change::Replace {
+                            id: self.id,
+                            html: self.to_html().await,
+                        },
+                    ));
+                }
+                if let TaskValue::Stable(options) | TaskValue::Unstable(options) =
+                    self.share.read().as_ref()
+                {
+                    // Keep the chosen options in the order they are offered in
+                    self.chosen = options
+                        .iter()
+                        .filter(|option| match **option == toggled {
+                            true => checked,
+                            false => self.chosen.contains(option),
+                        })
+                        .cloned()
+                        .collect();
+                }
+                Feedback::from(Change::Valid { id })
+                    .merged_with(Feedback::from(Change::ReplaceContent {
+                        id: self.message_id,
+                        html: Html(self.check().unwrap_or_default()),
+                    }))
+                    .unwrap()
+            }
+            _ => Feedback::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> Refresh for ChooseMany<S, T>
+where
+    S: ShareRead<Value = Vec<T>> + ShareUpdate + Send + Sync,
+    T: Clone + PartialEq + Display + Send + Sync,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        if self.share.updated(ids) {
+            self.reload();
+            Feedback::from(Change::Replace {
+                id: self.id,
+                html: self.to_html().await,
+            })
+        } else {
+            Feedback::new()
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> ToHtml for ChooseMany<S, T>
+where
+    S: ShareRead<Value = Vec<T>> + Send + Sync,
+    T: PartialEq + Display + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+
+        let checkboxes: Html = self
+            .options
+            .iter()
+            .map(|(id, option)| {
+                    let checked = if self.chosen.contains(option) {
+                        "checked"
+                    } else {
+                        ""
+                    };
+                    Html(format!(
+                        r#"
+                        <label class="checkbox">
+                            <input id="{id}" type="checkbox" onclick="update(this, this.checked.toString())" {checked}/>
+                            {option}
+                        </label>
+                    "#
+                    ))
+                })
+                .collect();
+
+        Html(format!(
+            r#"
+            <div id="{}" class="control">
+                {label}
+                {checkboxes}
+                <p id="{}" class="help is-danger">{}</p>
+            </div>
+        "#,
+            self.id,
+            self.message_id,
+            self.check().unwrap_or_default()
+        ))
+    }
+}
+
+/// The options in `share`, each with a fresh id for its input.
+fn options_with_ids<S, T>(share: &S) -> Vec<(Uuid, T)>
+where
+    S: ShareRead<Value = Vec<T>>,
+    T: Clone,
+{
+    match share.read().as_ref() {
+        TaskValue::Stable(options) | TaskValue::Unstable(options) => options
+            .iter()
+            .map(|option| (Uuid::new_v4(), option.clone()))
+            .collect(),
+        TaskValue::Error(_) | TaskValue::Empty => Vec::new(),
+    }
+}
+
+/// Whether `option` is still among the options in `share`.
+fn offers<S, T>(share: &S, option: &T) -> bool
+where
+    S: ShareRead<Value = Vec<T>>,
+    T: PartialEq,
+{
+    match share.read().as_ref() {
+        TaskValue::Stable(options) | TaskValue::Unstable(options) => options.contains(option),
+        TaskValue::Error(_) | TaskValue::Empty => false,
+    }
+}
+
+/// Describes a number of options, such as `1 option` or `2 options`.
+fn options(count: usize) -> String {
+    match count {
+        1 => String::from("1 option"),
+        count => format!("{count} options"),
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+
+use uuid::Uuid;
+
+use crate::html::Html;
+use crate::share::{ShareRead, ShareValue};
+use crate::task::edit::form::{FromForm, IntoForm, Widget};
+use crate::task::edit::{Edit, EditShared, EditValue};
+use crate::task::view::{View, ViewDisplay, ViewShared};
+use crate::task::TaskValue;
+
+/// A number between `MIN` and `MAX`, inclusive. Its input only offers values within these bounds,
+/// and entering a value outside of them turns it into a [`TaskValue::Error`]. Bounds that do not fit
+/// `T`, or where `MIN` is greater than `MAX`, are rejected at compile time.
+///
+/// With [`Widget::Slider`], the number is edited using a range input, next to which its value is
+/// shown.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Bounded<T, const MIN: i64, const MAX: i64>(T);
+
+impl<T, const MIN: i64, const MAX: i64> Bounded<T, MIN, MAX> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> Deref for Bounded<T, MIN, MAX> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> Display for Bounded<T, MIN, MAX>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Implements bounded numbers of type `$ty`, which are compared to the bounds as `$wide` and are
+/// entered in steps of `$step`.
+macro_rules! impl_bounded {
+    ($($ty:ty: $wide:ty, $step:literal;)*) => {
+        $(
+            impl<const MIN: i64, const MAX: i64> Bounded<$ty, MIN, MAX> {
+                /// Fails to compile when the bounds are empty or do not fit the type.
+                const VALID: () = {
+                    assert!(MIN <= MAX, "`MIN` must not be greater than `MAX`");
+                    assert!(
+                        MIN as $wide >= <$ty>::MIN as $wide && MAX as $wide <= <$ty>::MAX as $wide,
+                        concat!("`MIN` and `MAX` must fit in `", stringify!($ty), "`")
+                    );
+                };
+
+                /// Returns the bounded number, or `None` if `value` lies outside of the bounds.
+                pub fn new(value: $ty) -> Option<Self> {
+                    let () = Self::VALID;
+                    let wide = value as $wide;
+                    (wide >= MIN as $wide && wide <= MAX as $wide).then(|| Bounded(value))
+                }
+            }
+
+            impl<const MIN: i64, const MAX: i64> FromForm for Bounded<$ty, MIN, MAX> {
+                fn from_form(value: String) -> TaskValue<Self> {
+                    match value.parse::<$ty>() {
+                        Ok(value) => match Self::new(value) {
+                            Some(value) => TaskValue::Unstable(value),
+                            None => TaskValue::Error(format!("must be between {MIN} and {MAX}")),
+                        },
+                        // Numbers that do not fit the type, such as negative ones for unsigned types,
+                        // lie outside of the bounds as well
+                        Err(_) if value
+                            .parse::<f64>()
+                            .map_or(false, |value| value < MIN as f64 || value > MAX as f64) =>
+                        {
+                            TaskValue::Error(format!("must be between {MIN} and {MAX}"))
+                        }
+                        Err(error) => TaskValue::Error(error.to_string()),
+                    }
+                }
+            }
+
+            impl<const MIN: i64, const MAX: i64> IntoForm for Bounded<$ty, MIN, MAX> {
+                fn form_value(&self) -> Option<String> {
+                    Some(self.0.to_string())
+                }
+
+                fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+                    Html(format!(r#"
+                        <label for="{id}" class="label">{label}</label>
+                        <input id="{id}" type="number" class="input" min="{MIN}" max="{MAX}" step="{}" value="{}" oninput="update(this)"/>
+                    "#,
+                        $step,
+                        value.as_ref().map(ToString::to_string).unwrap_or_default()
+                    ))
+                }
+
+                fn into_widget(
+                    value: &TaskValue<Self>,
+                    id: &Uuid,
+                    label: &str,
+                    widget: Widget,
+                ) -> Html {
+                    match widget {
+                        Widget::Slider => Html(format!(r#"
+                            <label for="{id}" class="label">{label}</label>
+                            <input id="{id}" type="range" class="slider" min="{MIN}" max="{MAX}" step="{}" value="{value}" oninput="this.nextElementSibling.value = this.value; update(this)"/>
+                            <output for="{id}">{value}</output>
+                        "#,
+                            $step,
+                            value = value.as_ref().map(ToString::to_string).unwrap_or_default()
+                        )),
+                        Widget::TextArea | Widget::Password => Self::into_form(value, id, label),
+                    }
+                }
+            }
+
+            impl<const MIN: i64, const MAX: i64> Edit for Bounded<$ty, MIN, MAX> {
+                type Task = EditValue<ShareValue<Self>>;
+
+                fn edit(value: Option<Self>) -> Self::Task {
+                    let () = Self::VALID;
+                    EditValue::new(ShareValue::new(value))
+                }
+            }
+
+            impl<S, const MIN: i64, const MAX: i64> EditShared<S> for Bounded<$ty, MIN, MAX>
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+            {
+                type Task = EditValue<S>;
+
+                fn edit_shared(share: S) -> Self::Task {
+                    let () = Self::VALID;
+                    EditValue::new(share)
+                }
+            }
+
+            impl<const MIN: i64, const MAX: i64> View for Bounded<$ty, MIN, MAX> {
+                type Task = ViewDisplay<ShareValue<Self>>;
+
+                fn view(self) -> Self::Task {
+                    ViewDisplay::new(ShareValue::new(Some(self)))
+                }
+            }
+
+            impl<S, const MIN: i64, const MAX: i64> ViewShared<S> for Bounded<$ty, MIN, MAX>
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+            {
+                type Task = ViewDisplay<S>;
+
+                fn view_shared(share: S) -> Self::Task {
+                    ViewDisplay::new(share)
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded!(
+    u8: i128, "1";
+    u16: i128, "1";
+    u32: i128, "1";
+    u64: i128, "1";
+    usize: i128, "1";
+    i8: i128, "1";
+    i16: i128, "1";
+    i32: i128, "1";
+    i64: i128, "1";
+    i128: i128, "1";
+    isize: i128, "1";
+    f32: f64, "any";
+    f64: f64, "any";
+);
@@ -0,0 +1,89 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use uuid::Uuid;
+
+use crate::html::Html;
+use crate::share::{ShareRead, ShareValue};
+use crate::task::edit::form::{FromForm, IntoForm};
+use crate::task::edit::{Edit, EditShared, EditValue};
+use crate::task::view::{View, ViewDisplay, ViewShared};
+use crate::task::TaskValue;
+
+/// Implements the form traits for a date or time type, which is edited using the native input of
+/// type `$input`. Browsers send values in the first format, or in the second if the seconds are
+/// left out.
+macro_rules! impl_date {
+    ($($ty:ty: $input:literal, $format:literal, $short:literal;)*) => {
+        $(
+            impl FromForm for $ty {
+                fn from_form(value: String) -> TaskValue<Self> {
+                    match <$ty>::parse_from_str(&value, $format)
+                        .or_else(|_| <$ty>::parse_from_str(&value, $short))
+                    {
+                        Ok(value) => TaskValue::Unstable(value),
+                        Err(error) => TaskValue::Error(error.to_string()),
+                    }
+                }
+            }
+
+            impl IntoForm for $ty {
+                fn form_value(&self) -> Option<String> {
+                    Some(self.format($format).to_string())
+                }
+
+                fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+                    Html(format!(r#"
+                        <label for="{id}" class="label">{label}</label>
+                        <input id="{id}" type="{}" step="1" class="input" value="{}" oninput="update(this)"/>
+                    "#,
+                        $input,
+                        value.as_ref().map(|value| value.format($format).to_string()).unwrap_or_default()
+                    ))
+                }
+            }
+
+            impl Edit for $ty {
+                type Task = EditValue<ShareValue<$ty>>;
+
+                fn edit(value: Option<Self>) -> Self::Task {
+                    EditValue::new(ShareValue::new(value))
+                }
+            }
+
+            impl<S> EditShared<S> for $ty
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+            {
+                type Task = EditValue<S>;
+
+                fn edit_shared(share: S) -> Self::Task {
+                    EditValue::new(share)
+                }
+            }
+
+            impl View for $ty {
+                type Task = ViewDisplay<ShareValue<$ty>>;
+
+                fn view(self) -> Self::Task {
+                    ViewDisplay::new(ShareValue::new(Some(self)))
+                }
+            }
+
+            impl<S> ViewShared<S> for $ty
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+            {
+                type Task = ViewDisplay<S>;
+
+                fn view_shared(share: S) -> Self::Task {
+                    ViewDisplay::new(share)
+                }
+            }
+        )*
+    };
+}
+
+impl_date!(
+    NaiveDate: "date", "%Y-%m-%d", "%Y-%m-%d";
+    NaiveTime: "time", "%H:%M:%S%.f", "%H:%M";
+    NaiveDateTime: "datetime-local", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M";
+);
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::html::Html;
+use crate::share::{ShareRead, ShareValue};
+use crate::task::edit::form::{FromForm, IntoForm};
+use crate::task::edit::{Edit, EditShared, EditValue};
+use crate::task::TaskValue;
+
+impl FromForm for Duration {
+    fn from_form(value: String) -> TaskValue<Self> {
+        match parse_duration(&value) {
+            Some(duration) => TaskValue::Unstable(duration),
+            None => TaskValue::Error(format!(
+                "`{value}` is not a duration such as `1:30:00`, `5:00` or `30`"
+            )),
+        }
+    }
+}
+
+impl IntoForm for Duration {
+    fn form_value(&self) -> Option<String> {
+        Some(format_duration(self))
+    }
+
+    fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+        Html(format!(
+            r#"
+            <label for="{id}" class="label">{label}</label>
+            <input id="{id}" class="input" placeholder="h:mm:ss" value="{}" oninput="update(this)"/>
+        "#,
+            value.as_ref().map(format_duration).unwrap_or_default()
+        ))
+    }
+}
+
+impl Edit for Duration {
+    type Task = EditValue<ShareValue<Duration>>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditValue::new(ShareValue::new(value))
+    }
+}
+
+impl<S> EditShared<S> for Duration
+where
+    S: ShareRead<Value = Self> + Send + Sync,
+{
+    type Task = EditValue<S>;
+
+    fn edit_shared(share: S) -> Self::Task {
+        EditValue::new(share)
+    }
+}
+
+/// Formats `duration` as hours, minutes and seconds, such as `1:30:00` or `0:00:02.5`.
+pub(crate) fn format_duration(duration: &Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    let fraction = match duration.subsec_nanos() {
+        0 => String::new(),
+        nanos => format!(".{nanos:09}").trim_end_matches('0').to_owned(),
+    };
+    format!("{hours}:{minutes:02}:{seconds:02}{fraction}")
+}
+
+/// Parses durations such as `1:30:00`, `5:00` or `2.5`, where only the seconds may have a fraction.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut parts = value.trim().rsplit(':');
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    // Rejects negative, infinite and overly large numbers of seconds
+    let seconds = Duration::try_from_secs_f64(seconds).ok()?;
+    let minutes: u64 = parts.next().map(str::parse).transpose().ok()?.unwrap_or(0);
+    let hours: u64 = parts.next().map(str::parse).transpose().ok()?.unwrap_or(0);
+    match parts.next() {
+        None => {
+            let whole = hours
+                .checked_mul(3600)?
+                .checked_add(minutes.checked_mul(60)?)?;
+            Duration::from_secs(whole).checked_add(seconds)
+        }
+        Some(_) => None,
+    }
+}
@@ -1,9 +1,12 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
 pub use top_derive::Edit;
 
-use crate::share::{ShareValue, ShareVec};
+use crate::share::{ShareMap, ShareValue, ShareVec};
 use crate::task::edit::edit_shared::EditShared;
 use crate::task::edit::value::EditValue;
-use crate::task::edit::EditVec;
+use crate::task::edit::{EditMap, EditOption, EditVec};
 use crate::task::Value;
 
 pub trait Edit: Sized {
@@ -40,7 +43,7 @@ impl Edit for char {
 
 impl<T> Edit for Vec<T>
 where
-    T: EditShared<ShareValue<T>> + Clone + Send,
+    T: EditShared<ShareValue<T>> + Clone + Send + Sync,
     T::Task: Send + Sync,
 {
     type Task = EditVec<ShareVec<ShareValue<T>>, T::Task>;
@@ -49,3 +52,43 @@ where
         EditVec::new(ShareVec::new(value))
     }
 }
+
+impl<K, V> Edit for BTreeMap<K, V>
+where
+    K: EditShared<ShareValue<K>> + Ord + Clone + Send,
+    K::Task: Send + Sync,
+    V: EditShared<ShareValue<V>> + Clone + Send,
+    V::Task: Send + Sync,
+{
+    type Task = EditMap<ShareMap<Self, ShareValue<K>, ShareValue<V>>, K::Task, V::Task>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditMap::new(ShareMap::new(value))
+    }
+}
+
+impl<K, V> Edit for HashMap<K, V>
+where
+    K: EditShared<ShareValue<K>> + Eq + Hash + Clone + Send,
+    K::Task: Send + Sync,
+    V: EditShared<ShareValue<V>> + Clone + Send,
+    V::Task: Send + Sync,
+{
+    type Task = EditMap<ShareMap<Self, ShareValue<K>, ShareValue<V>>, K::Task, V::Task>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditMap::new(ShareMap::new(value))
+    }
+}
+
+impl<T> Edit for Option<T>
+where
+    T: Edit + Send,
+    T::Task: Send + Sync,
+{
+    type Task = EditOption<ShareValue<Option<T>>, T::Task>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditOption::new(ShareValue::new(None), value)
+    }
+}
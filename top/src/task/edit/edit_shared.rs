@@ -1,6 +1,9 @@
-use crate::share::{ShareChildren, ShareRead};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::share::{ShareChildren, ShareRead, ShareUpdate};
 use crate::task::edit::value::EditValue;
-use crate::task::edit::EditVec;
+use crate::task::edit::{Edit, EditMap, EditOption, EditVec};
 use crate::task::Value;
 
 pub trait EditShared<S>: Sized {
@@ -32,10 +35,10 @@ impl_edit_shared!(
 
 impl<S, T> EditShared<S> for Vec<T>
 where
-    T: EditShared<S::Child> + Clone,
+    T: EditShared<S::Child> + Clone + Send + Sync,
     T::Task: Send + Sync,
     S: ShareChildren + ShareRead<Value = Vec<T>> + Send + Sync,
-    S::Child: ShareRead<Value = T> + Clone,
+    S::Child: ShareRead<Value = T> + ShareUpdate + Clone,
 {
     type Task = EditVec<S, T::Task>;
 
@@ -43,3 +46,51 @@ where
         EditVec::new(share)
     }
 }
+
+impl<S, K, V, SK, SV> EditShared<S> for BTreeMap<K, V>
+where
+    K: EditShared<SK> + Ord + Clone,
+    K::Task: Send + Sync,
+    V: EditShared<SV> + Clone,
+    V::Task: Send + Sync,
+    S: ShareChildren<Child = (SK, SV)> + ShareRead<Value = Self> + ShareUpdate + Send + Sync,
+    SK: ShareRead<Value = K> + Clone,
+    SV: ShareRead<Value = V> + Clone,
+{
+    type Task = EditMap<S, K::Task, V::Task>;
+
+    fn edit_shared(share: S) -> Self::Task {
+        EditMap::new(share)
+    }
+}
+
+impl<S, K, V, SK, SV> EditShared<S> for HashMap<K, V>
+where
+    K: EditShared<SK> + Eq + Hash + Clone,
+    K::Task: Send + Sync,
+    V: EditShared<SV> + Clone,
+    V::Task: Send + Sync,
+    S: ShareChildren<Child = (SK, SV)> + ShareRead<Value = Self> + ShareUpdate + Send + Sync,
+    SK: ShareRead<Value = K> + Clone,
+    SV: ShareRead<Value = V> + Clone,
+{
+    type Task = EditMap<S, K::Task, V::Task>;
+
+    fn edit_shared(share: S) -> Self::Task {
+        EditMap::new(share)
+    }
+}
+
+impl<S, T> EditShared<S> for Option<T>
+where
+    T: Edit + Clone + Send,
+    T::Task: Send + Sync,
+    S: ShareRead<Value = Option<T>> + Send + Sync,
+{
+    type Task = EditOption<S, T::Task>;
+
+    fn edit_shared(share: S) -> Self::Task {
+        let value = share.read().as_ref().clone().into();
+        EditOption::new(share, value)
+    }
+}
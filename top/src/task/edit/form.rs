@@ -8,7 +8,37 @@ pub trait FromForm: Sized {
 }
 
 pub trait IntoForm: Sized {
+    /// The value as it appears in the form element, such that [`FromForm`] turns it back into the
+    /// same value, or `None` if the value must never be sent to the client.
+    ///
+    /// By default, values are never sent, so changes made elsewhere are not reflected in the form
+    /// element until it is rendered again.
+    fn form_value(&self) -> Option<String> {
+        None
+    }
+
     fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html;
+
+    /// Renders the value using `widget`, or the default form element if the type does not
+    /// support that widget.
+    fn into_widget(value: &TaskValue<Self>, id: &Uuid, label: &str, widget: Widget) -> Html {
+        let _ = widget;
+        Self::into_form(value, id, label)
+    }
+}
+
+/// Alternative input elements for editing values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Widget {
+    /// A text area spanning multiple lines.
+    TextArea,
+    /// A text input that hides what is typed.
+    Password,
+    /// A range input with a handle that can be dragged. Only [`Bounded`] numbers support this
+    /// widget, as the input needs bounds that lie within those of the number.
+    ///
+    /// [`Bounded`]: crate::task::edit::Bounded
+    Slider,
 }
 
 macro_rules! impl_from_form {
@@ -31,6 +61,10 @@ impl_from_form!(
 );
 
 impl IntoForm for String {
+    fn form_value(&self) -> Option<String> {
+        Some(self.clone())
+    }
+
     fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
         Html(format!(
             r#"
@@ -40,12 +74,36 @@ impl IntoForm for String {
             value.clone().unwrap_or_default()
         ))
     }
+
+    fn into_widget(value: &TaskValue<Self>, id: &Uuid, label: &str, widget: Widget) -> Html {
+        match widget {
+            Widget::TextArea => Html(format!(
+                r#"
+                <label for="{id}" class="label">{label}</label>
+                <textarea id="{id}" class="textarea" oninput="update(this)">{}</textarea>
+            "#,
+                value.clone().unwrap_or_default()
+            )),
+            // The password is never sent to the client, so the input starts out empty
+            Widget::Password => Html(format!(
+                r#"
+                <label for="{id}" class="label">{label}</label>
+                <input id="{id}" type="password" class="input" oninput="update(this)"/>
+            "#
+            )),
+            Widget::Slider => Self::into_form(value, id, label),
+        }
+    }
 }
 
 macro_rules! impl_into_form_for_number {
     ($($ty:ty),*) => {
         $(
             impl IntoForm for $ty {
+                fn form_value(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+
                 fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
                     Html(format!(r#"
                         <label for="{id}" class="label">{label}</label>
@@ -64,6 +122,10 @@ impl_into_form_for_number!(
 );
 
 impl IntoForm for bool {
+    fn form_value(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+
     fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
         let checked = value
             .as_ref()
@@ -81,6 +143,10 @@ impl IntoForm for bool {
 }
 
 impl IntoForm for char {
+    fn form_value(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+
     fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
         Html(format!(
             r#"
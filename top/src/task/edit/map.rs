@@ -0,0 +1,254 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use futures::future;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{self, ShareChildren, ShareCreate, ShareRead, ShareUpdate};
+use crate::task::edit::edit_shared;
+use crate::task::edit::edit_shared::EditShared;
+use crate::task::edit::vec::Row;
+use crate::task::{TaskValue, Value, WithLabel};
+
+/// Editor for maps, which shows an editor for the key and an editor for the value of each entry.
+/// Entries can be added and removed, and the value of the map is an error as long as two entries
+/// share the same key.
+#[derive(Clone, Debug)]
+pub struct EditMap<S, K, V> {
+    container_id: Uuid,
+    elements_id: Uuid,
+    add_id: Uuid,
+    message_id: Uuid,
+    rows: Vec<Row>,
+    share: S,
+    tasks: Vec<(K, V)>,
+    label: Option<String>,
+}
+
+impl<S, K, V, SK, SV> EditMap<S, K, V>
+where
+    S: ShareChildren<Child = (SK, SV)>,
+    SK: ShareRead<Value = K::Output> + Clone,
+    SV: ShareRead<Value = V::Output> + Clone,
+    K: Value,
+    K::Output: EditShared<SK, Task = K>,
+    V: Value,
+    V::Output: EditShared<SV, Task = V>,
+{
+    pub fn new(share: S) -> Self {
+        let tasks = Self::tasks(&share);
+        let rows = tasks.iter().map(|_| Row::new()).collect();
+        EditMap {
+            container_id: Uuid::new_v4(),
+            elements_id: Uuid::new_v4(),
+            add_id: Uuid::new_v4(),
+            message_id: Uuid::new_v4(),
+            rows,
+            share,
+            tasks,
+            label: None,
+        }
+    }
+
+    fn tasks(share: &S) -> Vec<(K, V)> {
+        share
+            .children()
+            .iter()
+            .cloned()
+            .map(|(key, value)| (edit_shared(key), edit_shared(value)))
+            .collect()
+    }
+}
+
+impl<S, K, V> EditMap<S, K, V>
+where
+    S: ShareRead + Send + Sync,
+    K: Send + Sync,
+    V: Send + Sync,
+{
+    /// The message explaining why the entries do not form a valid map, if they do not.
+    fn message(&self) -> String {
+        match self.share.read().as_ref() {
+            TaskValue::Error(error) => error.clone(),
+            _ => String::new(),
+        }
+    }
+}
+
+impl<S, K, V> WithLabel for EditMap<S, K, V> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S, K, V> Value for EditMap<S, K, V>
+where
+    S: ShareRead + Send + Sync,
+    S::Value: Clone,
+    K: Send + Sync,
+    V: Send + Sync,
+{
+    type Output = S::Value;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        self.share.read().as_ref().clone()
+    }
+}
+
+#[async_trait]
+impl<S, K, V, SK, SV> Handler for EditMap<S, K, V>
+where
+    S: ShareChildren<Child = (SK, SV)> + ShareRead + ShareUpdate + Send + Sync,
+    SK: ShareCreate,
+    SV: ShareCreate,
+    K: Handler + Send + Sync,
+    V: Handler + Send + Sync,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        match event {
+            Event::Press { id } if id == self.add_id => {
+                // Add a new entry
+                self.share
+                    .children()
+                    .push((SK::create(TaskValue::Empty), SV::create(TaskValue::Empty)));
+                Feedback::update_share(self.share.id())
+            }
+            Event::Press { id } if self.rows.iter().any(|row| row.remove_id == id) => {
+                // Remove an existing entry
+                let index = self
+                    .rows
+                    .iter()
+                    .position(|row| row.remove_id == id)
+                    .unwrap();
+                self.share.children().remove(index);
+                Feedback::update_share(self.share.id())
+            }
+            _ => {
+                let feedback: Feedback =
+                    future::join_all(self.tasks.iter_mut().map(|(key, value)| async {
+                        key.on_event(event.clone())
+                            .await
+                            .merged_with(value.on_event(event.clone()).await)
+                            .unwrap()
+                    }))
+                    .await
+                    .into_iter()
+                    .collect();
+                if feedback.shares().is_empty() {
+                    return feedback;
+                }
+
+                // An entry changed, which may have made the keys (non-)unique
+                feedback
+                    .merged_with(Feedback::from(Change::ReplaceContent {
+                        id: self.message_id,
+                        html: Html(self.message()),
+                    }))
+                    .unwrap()
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S, K, V, SK, SV> Refresh for EditMap<S, K, V>
+where
+    S: ShareChildren<Child = (SK, SV)> + ShareRead + ShareUpdate + Send + Sync,
+    SK: ShareRead<Value = K::Output> + Clone,
+    SV: ShareRead<Value = V::Output> + Clone,
+    K: Value + Refresh + ToHtml + Send + Sync,
+    K::Output: EditShared<SK, Task = K>,
+    V: Value + Refresh + ToHtml + Send + Sync,
+    V::Output: EditShared<SV, Task = V>,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        if share::is_updated(ids, self.share.id()) {
+            // Entries were added or removed, start over with the new ones
+            self.tasks = Self::tasks(&self.share);
+            self.rows = self.tasks.iter().map(|_| Row::new()).collect();
+            Feedback::from(Change::Replace {
+                id: self.container_id,
+                html: self.to_html().await,
+            })
+        } else if self.share.updated(ids) {
+            let feedback: Feedback =
+                future::join_all(self.tasks.iter_mut().map(|(key, value)| async {
+                    key.refresh(ids)
+                        .await
+                        .merged_with(value.refresh(ids).await)
+                        .unwrap()
+                }))
+                .await
+                .into_iter()
+                .collect();
+            feedback
+                .merged_with(Feedback::from(Change::ReplaceContent {
+                    id: self.message_id,
+                    html: Html(self.message()),
+                }))
+                .unwrap()
+        } else {
+            Feedback::new()
+        }
+    }
+}
+
+#[async_trait]
+impl<S, K, V> ToHtml for EditMap<S, K, V>
+where
+    S: ShareRead + Send + Sync,
+    K: ToHtml + Send + Sync,
+    V: ToHtml + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let children: Html = future::join_all(
+            self.tasks
+                .iter()
+                .map(|(key, value)| future::join(key.to_html(), value.to_html())),
+        )
+        .await
+        .into_iter()
+        .zip(&self.rows)
+        .map(|((key, value), row)| {
+            Html(format!(
+                r#"
+                    <div id={} class="columns">
+                        <div class="column">{key}</div>
+                        <div class="column">{value}</div>
+                        <div class="column is-narrow">
+                            <button id="{}" class="button" type="button" onclick="press(this)">-</button>
+                        </div>
+                    </div>
+                "#,
+                row.container_id, row.remove_id
+            ))
+        })
+        .collect();
+
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+
+        Html(format!(
+            r#"
+                <div id="{}" class="column">
+                    {label}
+                    <div id="{}" class="column">{children}</div>
+                    <p id="{}" class="help is-danger">{}</p>
+                    <button id="{}" class="button" type="button" onclick="press(this)">+</button>
+                </div>
+            "#,
+            self.container_id,
+            self.elements_id,
+            self.message_id,
+            self.message(),
+            self.add_id
+        ))
+    }
+}
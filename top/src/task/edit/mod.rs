@@ -1,13 +1,35 @@
+pub use bounded::Bounded;
+pub(crate) use duration::format_duration;
+pub use edit::Edit;
+pub use edit_shared::EditShared;
+pub use form::Widget;
+pub use map::EditMap;
+pub use option::EditOption;
+pub use parsed::Parsed;
+pub use text::{Email, Multiline, Secret, Url};
+pub use tuple::EditTuple;
+pub use upload::{EditUpload, Upload};
+pub use validate::{Length, Validate};
 pub use value::EditValue;
+pub(crate) use vec::retained;
 pub use vec::EditVec;
 
 use crate::share::ShareRead;
-use crate::task::edit::edit::Edit;
-use crate::task::edit::edit_shared::EditShared;
 
+mod bounded;
+#[cfg(feature = "chrono")]
+mod date;
+mod duration;
 mod edit;
 mod edit_shared;
 mod form;
+mod map;
+mod option;
+mod parsed;
+mod text;
+mod tuple;
+mod upload;
+mod validate;
 mod value;
 mod vec;
 
@@ -0,0 +1,170 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareRead, ShareUpdate, ShareWrite};
+use crate::task::edit::Edit;
+use crate::task::{TaskValue, Value, WithLabel};
+
+/// Editor for optional values, with a toggle that shows or hides the editor for the value itself.
+/// When the toggle is off, the value is `None` rather than empty.
+#[derive(Clone, Debug)]
+pub struct EditOption<S, T> {
+    id: Uuid,
+    toggle_id: Uuid,
+    task_id: Uuid,
+    share: S,
+    written: bool,
+    task: Option<T>,
+    label: Option<String>,
+}
+
+impl<S, T> EditOption<S, T>
+where
+    T: Value,
+    T::Output: Edit<Task = T>,
+{
+    pub fn new(share: S, value: Option<Option<T::Output>>) -> Self {
+        EditOption {
+            id: Uuid::new_v4(),
+            toggle_id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            share,
+            written: false,
+            task: value.flatten().map(|value| T::Output::edit(Some(value))),
+            label: None,
+        }
+    }
+}
+
+impl<S, T> WithLabel for EditOption<S, T> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S, T> Value for EditOption<S, T>
+where
+    S: Send + Sync,
+    T: Value + Send + Sync,
+{
+    type Output = Option<T::Output>;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        match &self.task {
+            None => TaskValue::Unstable(None),
+            Some(task) => match task.value().await {
+                TaskValue::Stable(value) => TaskValue::Stable(Some(value)),
+                TaskValue::Unstable(value) => TaskValue::Unstable(Some(value)),
+                TaskValue::Error(error) => TaskValue::Error(error),
+                TaskValue::Empty => TaskValue::Empty,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> Handler for EditOption<S, T>
+where
+    S: ShareWrite<Value = Option<T::Output>> + ShareUpdate + Send + Sync,
+    T: Value + Handler + ToHtml + Send + Sync,
+    T::Output: Edit<Task = T>,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        let feedback = match event {
+            Event::Update { id, value } if id == self.toggle_id => {
+                // Show an empty editor when the value is set, or get rid of it when it is unset
+                self.task = match value.parse() {
+                    Ok(true) => Some(T::Output::edit(None)),
+                    Ok(false) => None,
+                    Err(_) => return Feedback::from(Change::Invalid { id }),
+                };
+                let html = match &self.task {
+                    None => Html::default(),
+                    Some(task) => task.to_html().await,
+                };
+                Feedback::from(Change::ReplaceContent {
+                    id: self.task_id,
+                    html,
+                })
+            }
+            event => {
+                let feedback = match &mut self.task {
+                    None => Feedback::new(),
+                    Some(task) => task.on_event(event).await,
+                };
+                if feedback.shares().is_empty() {
+                    return feedback;
+                }
+                feedback
+            }
+        };
+
+        let value = self.value().await;
+        self.share.write(value);
+        self.written = true;
+        feedback
+            .merged_with(Feedback::update_share(self.share.id()))
+            .unwrap()
+    }
+}
+
+#[async_trait]
+impl<S, T> Refresh for EditOption<S, T>
+where
+    S: ShareRead<Value = Option<T::Output>> + ShareUpdate + Send + Sync,
+    T: Value + Refresh + ToHtml + Send + Sync,
+    T::Output: Edit<Task = T> + Clone,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        let written = std::mem::replace(&mut self.written, false);
+        if self.share.updated(ids) && !written {
+            // Someone else changed the value, start over with the new one
+            let value: Option<_> = self.share.read().as_ref().clone().into();
+            self.task = value.flatten().map(|value| T::Output::edit(Some(value)));
+            Feedback::from(Change::Replace {
+                id: self.id,
+                html: self.to_html().await,
+            })
+        } else {
+            match &mut self.task {
+                None => Feedback::new(),
+                Some(task) => task.refresh(ids).await,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> ToHtml for EditOption<S, T>
+where
+    S: Send + Sync,
+    T: ToHtml + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let (checked, task) = match &self.task {
+            None => ("", Html::default()),
+            Some(task) => ("checked", task.to_html().await),
+        };
+        Html(format!(
+            r#"
+            <div id="{}">
+                <label class="checkbox">
+                    <input id="{}" type="checkbox" onclick="update(this, this.checked.toString())" {checked}/>
+                    {}
+                </label>
+                <div id="{}">{task}</div>
+            </div>
+        "#,
+            self.id,
+            self.toggle_id,
+            self.label.as_deref().unwrap_or_default(),
+            self.task_id
+        ))
+    }
+}
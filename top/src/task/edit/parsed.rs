@@ -0,0 +1,133 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::html::Html;
+use crate::share::{ShareRead, ShareValue};
+use crate::task::edit::form::{FromForm, IntoForm};
+use crate::task::edit::{Edit, EditShared, EditValue};
+use crate::task::view::{View, ViewDisplay, ViewShared};
+use crate::task::TaskValue;
+
+/// Wraps any type that can be parsed from and displayed as a string, so it can be edited using a
+/// text input. Input that cannot be parsed turns the value into a [`TaskValue::Error`] describing
+/// why.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Parsed<T>(pub T);
+
+impl<T> Parsed<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Parsed<T> {
+    fn from(value: T) -> Self {
+        Parsed(value)
+    }
+}
+
+impl<T> Deref for Parsed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Parsed<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Display for Parsed<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> FromForm for Parsed<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn from_form(value: String) -> TaskValue<Self> {
+        match value.parse() {
+            Ok(value) => TaskValue::Unstable(Parsed(value)),
+            Err(error) => TaskValue::Error(error.to_string()),
+        }
+    }
+}
+
+impl<T> IntoForm for Parsed<T>
+where
+    T: Display,
+{
+    fn form_value(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+
+    fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+        Html(format!(
+            r#"
+            <label for="{id}" class="label">{label}</label>
+            <input id="{id}" class="input" value="{}" oninput="update(this)"/>
+        "#,
+            value.as_ref().map(ToString::to_string).unwrap_or_default()
+        ))
+    }
+}
+
+impl<T> Edit for Parsed<T>
+where
+    T: FromStr + Display + Clone + Send + Sync,
+    T::Err: Display,
+{
+    type Task = EditValue<ShareValue<Parsed<T>>>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditValue::new(ShareValue::new(value))
+    }
+}
+
+impl<S, T> EditShared<S> for Parsed<T>
+where
+    S: ShareRead<Value = Self> + Send + Sync,
+    T: FromStr + Display + Clone + Send + Sync,
+    T::Err: Display,
+{
+    type Task = EditValue<S>;
+
+    fn edit_shared(share: S) -> Self::Task {
+        EditValue::new(share)
+    }
+}
+
+impl<T> View for Parsed<T>
+where
+    T: Display + Clone + Send + Sync,
+{
+    type Task = ViewDisplay<ShareValue<Parsed<T>>>;
+
+    fn view(self) -> Self::Task {
+        ViewDisplay::new(ShareValue::new(Some(self)))
+    }
+}
+
+impl<S, T> ViewShared<S> for Parsed<T>
+where
+    S: ShareRead<Value = Self> + Send + Sync,
+    T: Display + Clone + Send + Sync,
+{
+    type Task = ViewDisplay<S>;
+
+    fn view_shared(share: S) -> Self::Task {
+        ViewDisplay::new(share)
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::html::Html;
+use crate::share::{ShareRead, ShareValue};
+use crate::task::edit::form::{FromForm, IntoForm};
+use crate::task::edit::{Edit, EditShared, EditValue};
+use crate::task::view::{View, ViewDisplay, ViewShared};
+use crate::task::TaskValue;
+
+/// Text spanning multiple lines, which is edited using a text area.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Multiline(pub String);
+
+/// Text that is hidden while typing, such as a password. Its value is never sent back to the
+/// client, and viewing it only shows that it has been entered.
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Secret(pub String);
+
+/// An email address, such as `someone@example.com`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Email(pub String);
+
+/// An absolute URL, such as `https://example.com/`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Url(pub String);
+
+/// Implements the conversions and the editors and viewers shared by all string wrappers.
+macro_rules! impl_text {
+    ($($ty:ident),*) => {
+        $(
+            impl $ty {
+                pub fn into_inner(self) -> String {
+                    self.0
+                }
+            }
+
+            impl From<String> for $ty {
+                fn from(value: String) -> Self {
+                    $ty(value)
+                }
+            }
+
+            impl From<&str> for $ty {
+                fn from(value: &str) -> Self {
+                    $ty(value.to_owned())
+                }
+            }
+
+            impl Deref for $ty {
+                type Target = String;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl DerefMut for $ty {
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    &mut self.0
+                }
+            }
+
+            impl<S> EditShared<S> for $ty
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+            {
+                type Task = EditValue<S>;
+
+                fn edit_shared(share: S) -> Self::Task {
+                    EditValue::new(share)
+                }
+            }
+
+            impl View for $ty {
+                type Task = ViewDisplay<ShareValue<$ty>>;
+
+                fn view(self) -> Self::Task {
+                    ViewDisplay::new(ShareValue::new(Some(self)))
+                }
+            }
+
+            impl<S> ViewShared<S> for $ty
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+            {
+                type Task = ViewDisplay<S>;
+
+                fn view_shared(share: S) -> Self::Task {
+                    ViewDisplay::new(share)
+                }
+            }
+        )*
+    };
+}
+
+impl_text!(Multiline, Secret, Email, Url);
+
+// Text is valid even when left untouched, while addresses start out empty so that they are only
+// valid once entered.
+
+impl Edit for Multiline {
+    type Task = EditValue<ShareValue<Multiline>>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditValue::new(ShareValue::new(Some(value.unwrap_or_default())))
+    }
+}
+
+impl Edit for Secret {
+    type Task = EditValue<ShareValue<Secret>>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditValue::new(ShareValue::new(Some(value.unwrap_or_default())))
+    }
+}
+
+impl Edit for Email {
+    type Task = EditValue<ShareValue<Email>>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditValue::new(ShareValue::new(value))
+    }
+}
+
+impl Edit for Url {
+    type Task = EditValue<ShareValue<Url>>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditValue::new(ShareValue::new(value))
+    }
+}
+
+impl Display for Multiline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Display for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("********")
+    }
+}
+
+impl Debug for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Secret").field(&format_args!("..")).finish()
+    }
+}
+
+impl Display for Email {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Display for Url {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl FromForm for Multiline {
+    fn from_form(value: String) -> TaskValue<Self> {
+        TaskValue::Unstable(Multiline(value))
+    }
+}
+
+impl FromForm for Secret {
+    fn from_form(value: String) -> TaskValue<Self> {
+        TaskValue::Unstable(Secret(value))
+    }
+}
+
+impl FromForm for Email {
+    fn from_form(value: String) -> TaskValue<Self> {
+        static EMAIL: OnceLock<Regex> = OnceLock::new();
+        let email = EMAIL.get_or_init(|| Regex::new(r"^[^@\s]+@[^@\s.]+(\.[^@\s.]+)+$").unwrap());
+        if email.is_match(&value) {
+            TaskValue::Unstable(Email(value))
+        } else {
+            TaskValue::Error(format!("`{value}` is not an email address"))
+        }
+    }
+}
+
+impl FromForm for Url {
+    fn from_form(value: String) -> TaskValue<Self> {
+        static URL: OnceLock<Regex> = OnceLock::new();
+        let url = URL.get_or_init(|| {
+            Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*://[^\s/?#]+([/?#]\S*)?$").unwrap()
+        });
+        if url.is_match(&value) {
+            TaskValue::Unstable(Url(value))
+        } else {
+            TaskValue::Error(format!("`{value}` is not a URL"))
+        }
+    }
+}
+
+impl IntoForm for Multiline {
+    fn form_value(&self) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+        Html(format!(
+            r#"
+            <label for="{id}" class="label">{label}</label>
+            <textarea id="{id}" class="textarea" oninput="update(this)">{}</textarea>
+        "#,
+            value
+                .as_ref()
+                .map(|value| value.0.as_str())
+                .unwrap_or_default()
+        ))
+    }
+}
+
+impl IntoForm for Secret {
+    fn form_value(&self) -> Option<String> {
+        None
+    }
+
+    fn into_form(_value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+        Html(format!(
+            r#"
+            <label for="{id}" class="label">{label}</label>
+            <input id="{id}" type="password" class="input" oninput="update(this)"/>
+        "#
+        ))
+    }
+}
+
+impl IntoForm for Email {
+    fn form_value(&self) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+        Html(format!(
+            r#"
+            <label for="{id}" class="label">{label}</label>
+            <input id="{id}" type="email" class="input" value="{}" oninput="update(this)"/>
+        "#,
+            value
+                .as_ref()
+                .map(|value| value.0.as_str())
+                .unwrap_or_default()
+        ))
+    }
+}
+
+impl IntoForm for Url {
+    fn form_value(&self) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    fn into_form(value: &TaskValue<Self>, id: &Uuid, label: &str) -> Html {
+        Html(format!(
+            r#"
+            <label for="{id}" class="label">{label}</label>
+            <input id="{id}" type="url" class="input" value="{}" oninput="update(this)"/>
+        "#,
+            value
+                .as_ref()
+                .map(|value| value.0.as_str())
+                .unwrap_or_default()
+        ))
+    }
+}
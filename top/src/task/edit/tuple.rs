@@ -0,0 +1,201 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareRead, ShareUpdate, ShareValue, ShareWrite};
+use crate::task::edit::{Edit, EditShared};
+use crate::task::{nested, TaskValue, Value, WithLabel};
+
+/// Editor for tuples, which shows the editors of its components side by side. `T` is the tuple of
+/// the tasks editing each component.
+#[derive(Clone, Debug)]
+pub struct EditTuple<S, T> {
+    id: Uuid,
+    share: S,
+    written: bool,
+    tasks: T,
+    label: Option<String>,
+}
+
+impl<S, T> WithLabel for EditTuple<S, T> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+macro_rules! impl_edit_tuple {
+    ($(($($index:tt $task:ident $value:ident),+)),+) => {
+        $(
+            impl<S, $($task),+> EditTuple<S, ($($task,)+)>
+            where
+                $($task: Value, $task::Output: Edit<Task = $task>,)+
+            {
+                pub fn new(share: S, value: Option<($($task::Output,)+)>) -> Self {
+                    EditTuple {
+                        id: Uuid::new_v4(),
+                        share,
+                        written: false,
+                        tasks: Self::tasks(value),
+                        label: None,
+                    }
+                }
+
+                fn tasks(value: Option<($($task::Output,)+)>) -> ($($task,)+) {
+                    match value {
+                        Some(($($value,)+)) => ($($task::Output::edit(Some($value)),)+),
+                        None => ($($task::Output::edit(None),)+),
+                    }
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> Value for EditTuple<S, ($($task,)+)>
+            where
+                S: Send + Sync,
+                $($task: Value + Send + Sync, $task::Output: Send,)+
+            {
+                type Output = ($($task::Output,)+);
+
+                async fn value(&self) -> TaskValue<Self::Output> {
+                    let value = TaskValue::Stable(())$(.and(self.tasks.$index.value().await))+;
+                    match value {
+                        TaskValue::Stable(nested!(() ; $($value)+)) => {
+                            TaskValue::Stable(($($value,)+))
+                        }
+                        TaskValue::Unstable(nested!(() ; $($value)+)) => {
+                            TaskValue::Unstable(($($value,)+))
+                        }
+                        TaskValue::Error(error) => TaskValue::Error(error),
+                        TaskValue::Empty => TaskValue::Empty,
+                    }
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> Handler for EditTuple<S, ($($task,)+)>
+            where
+                S: ShareWrite<Value = ($($task::Output,)+)> + ShareUpdate + Send + Sync,
+                $($task: Value + Handler + Send + Sync, $task::Output: Send,)+
+            {
+                async fn on_event(&mut self, event: Event) -> Feedback {
+                    let feedback = Feedback::new();
+                    $(
+                        let component = self.tasks.$index.on_event(event.clone()).await;
+                        let feedback = feedback.merged_with(component).unwrap();
+                    )+
+                    if feedback.shares().is_empty() {
+                        return feedback;
+                    }
+
+                    // One of the components changed, so the tuple as a whole did as well
+                    let value = self.value().await;
+                    self.share.write(value);
+                    self.written = true;
+                    feedback
+                        .merged_with(Feedback::update_share(self.share.id()))
+                        .unwrap()
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> Refresh for EditTuple<S, ($($task,)+)>
+            where
+                S: ShareRead<Value = ($($task::Output,)+)> + ShareUpdate + Send + Sync,
+                $(
+                    $task: Value + Refresh + ToHtml + Send + Sync,
+                    $task::Output: Edit<Task = $task> + Clone,
+                )+
+            {
+                async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+                    let written = std::mem::replace(&mut self.written, false);
+                    if self.share.updated(ids) && !written {
+                        // Someone else changed the tuple, start over with its new value
+                        let value = self.share.read().as_ref().clone();
+                        self.tasks = Self::tasks(value.into());
+                        Feedback::from(Change::Replace {
+                            id: self.id,
+                            html: self.to_html().await,
+                        })
+                    } else {
+                        let feedback = Feedback::new();
+                        $(
+                            let component = self.tasks.$index.refresh(ids).await;
+                            let feedback = feedback.merged_with(component).unwrap();
+                        )+
+                        feedback
+                    }
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> ToHtml for EditTuple<S, ($($task,)+)>
+            where
+                S: Send + Sync,
+                $($task: ToHtml + Send + Sync,)+
+            {
+                async fn to_html(&self) -> Html {
+                    let label = self
+                        .label
+                        .as_ref()
+                        .map(|label| format!(r#"<label class="label">{label}</label>"#))
+                        .unwrap_or_default();
+                    let components: Html = vec![
+                        $(Html(format!(
+                            r#"<div class="column">{}</div>"#,
+                            self.tasks.$index.to_html().await
+                        )),)+
+                    ]
+                    .into_iter()
+                    .collect();
+                    Html(format!(
+                        r#"<div id="{}">{label}<div class="columns">{components}</div></div>"#,
+                        self.id
+                    ))
+                }
+            }
+
+            impl<$($task),+> Edit for ($($task,)+)
+            where
+                $($task: Edit + Send, $task::Task: Send + Sync,)+
+            {
+                type Task = EditTuple<ShareValue<Self>, ($($task::Task,)+)>;
+
+                fn edit(value: Option<Self>) -> Self::Task {
+                    Self::Task::new(ShareValue::new(None), value)
+                }
+            }
+
+            impl<S, $($task),+> EditShared<S> for ($($task,)+)
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+                $($task: Edit + Clone + Send, $task::Task: Send + Sync,)+
+            {
+                type Task = EditTuple<S, ($($task::Task,)+)>;
+
+                fn edit_shared(share: S) -> Self::Task {
+                    let value = share.read().as_ref().clone().into();
+                    Self::Task::new(share, value)
+                }
+            }
+        )+
+    };
+}
+
+impl_edit_tuple!(
+    (0 T0 a),
+    (0 T0 a, 1 T1 b),
+    (0 T0 a, 1 T1 b, 2 T2 c),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i, 9 T9 j),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i, 9 T9 j, 10 T10 k),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i, 9 T9 j, 10 T10 k, 11 T11 l)
+);
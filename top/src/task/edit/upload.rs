@@ -0,0 +1,258 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{escape, Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareRead, ShareUpdate, ShareValue, ShareWrite};
+use crate::task::edit::{Edit, EditShared};
+use crate::task::{TaskValue, Value, WithLabel};
+
+/// A file uploaded by the user.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Upload {
+    pub name: String,
+    pub mime: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Editor for uploading a file, using a file input. The content of the file is sent to the server
+/// in chunks as soon as it is chosen, and the file is stored once all of them are received.
+#[derive(Clone, Debug)]
+pub struct EditUpload<S> {
+    id: Uuid,
+    name_id: Uuid,
+    message_id: Uuid,
+    share: S,
+    max_size: Option<usize>,
+    partial: Option<PartialUpload>,
+    label: Option<String>,
+}
+
+/// A file of which only the first chunks have been received.
+#[derive(Clone, Debug)]
+struct PartialUpload {
+    name: String,
+    mime: String,
+    size: usize,
+    bytes: Vec<u8>,
+}
+
+impl<S> EditUpload<S> {
+    pub fn new(share: S) -> Self {
+        EditUpload {
+            id: Uuid::new_v4(),
+            name_id: Uuid::new_v4(),
+            message_id: Uuid::new_v4(),
+            share,
+            max_size: None,
+            partial: None,
+            label: None,
+        }
+    }
+
+    /// Refuses files larger than `max_size` bytes. Files are checked by the browser before they are
+    /// sent, and by the server before their content is received.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+}
+
+impl<S> EditUpload<S>
+where
+    S: ShareRead<Value = Upload>,
+{
+    /// The name of the uploaded file and the message explaining why it was refused, if any.
+    fn status(&self) -> (String, String) {
+        match self.share.read().as_ref() {
+            TaskValue::Stable(upload) | TaskValue::Unstable(upload) => {
+                (escape(&upload.name), String::new())
+            }
+            TaskValue::Error(error) => (String::new(), escape(error)),
+            TaskValue::Empty => (String::new(), String::new()),
+        }
+    }
+}
+
+impl<S> EditUpload<S> {
+    /// Adds a chunk of the file starting at `offset` to the file received so far, returning the
+    /// value of the editor once the whole file is received or the upload fails.
+    fn receive(
+        &mut self,
+        name: String,
+        mime: String,
+        size: usize,
+        offset: usize,
+        bytes: &[u8],
+    ) -> Option<TaskValue<Upload>> {
+        if offset == 0 {
+            if let Some(max_size) = self.max_size.filter(|max_size| size > *max_size) {
+                self.partial = None;
+                return Some(TaskValue::Error(format!(
+                    "`{name}` is larger than the limit of {max_size} bytes"
+                )));
+            }
+            self.partial = Some(PartialUpload {
+                name,
+                mime,
+                size,
+                bytes: Vec::new(),
+            });
+        }
+        let partial = match &mut self.partial {
+            Some(partial)
+                if partial.bytes.len() == offset
+                    && partial.size == size
+                    && partial.size - offset >= bytes.len() =>
+            {
+                partial
+            }
+            _ => {
+                self.partial = None;
+                return Some(TaskValue::Error(String::from("The upload was interrupted")));
+            }
+        };
+        partial.bytes.extend_from_slice(bytes);
+        if partial.bytes.len() < partial.size {
+            return None;
+        }
+        let partial = self.partial.take()?;
+        Some(TaskValue::Unstable(Upload {
+            name: partial.name,
+            mime: partial.mime,
+            bytes: partial.bytes,
+        }))
+    }
+}
+
+impl<S> WithLabel for EditUpload<S> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S> Value for EditUpload<S>
+where
+    S: ShareRead<Value = Upload> + Send + Sync,
+{
+    type Output = Upload;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        self.share.read().as_ref().clone()
+    }
+}
+
+#[async_trait]
+impl<S> Handler for EditUpload<S>
+where
+    S: ShareWrite<Value = Upload> + ShareUpdate + Send + Sync,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        match event {
+            Event::Upload {
+                id,
+                name,
+                mime,
+                size,
+                offset,
+                bytes,
+            } if id == self.id => {
+                let value = match self.receive(name, mime, size, offset, &bytes) {
+                    Some(value) => value,
+                    // Wait for the rest of the file
+                    None => return Feedback::new(),
+                };
+                let change = match value {
+                    TaskValue::Error(_) => Change::Invalid { id },
+                    _ => Change::Valid { id },
+                };
+                self.share.write(value);
+                let feedback = Feedback::update_share(self.share.id());
+                feedback.merged_with(Feedback::from(change)).unwrap()
+            }
+            _ => Feedback::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> Refresh for EditUpload<S>
+where
+    S: ShareRead<Value = Upload> + ShareUpdate + Send + Sync,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        if self.share.updated(ids) {
+            // The input itself cannot be changed, so only show which file was uploaded
+            let (name, message) = self.status();
+            let name = Feedback::from(Change::ReplaceContent {
+                id: self.name_id,
+                html: Html(name),
+            });
+            let message = Feedback::from(Change::ReplaceContent {
+                id: self.message_id,
+                html: Html(message),
+            });
+            name.merged_with(message).unwrap()
+        } else {
+            Feedback::new()
+        }
+    }
+}
+
+#[async_trait]
+impl<S> ToHtml for EditUpload<S>
+where
+    S: ShareRead<Value = Upload> + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label for="{}" class="label">{label}</label>"#, self.id))
+            .unwrap_or_default();
+        let (name, message) = self.status();
+        let max = self
+            .max_size
+            .map(|max_size| format!(r#"data-max="{max_size}""#))
+            .unwrap_or_default();
+        Html(format!(
+            r#"
+            {label}
+            <div class="file has-name">
+                <label class="file-label">
+                    <input id="{}" type="file" class="file-input" data-name="{}" data-message="{}" {max} onchange="upload(this)"/>
+                    <span class="file-cta">
+                        <span class="file-label">Choose a file…</span>
+                    </span>
+                    <span id="{}" class="file-name">{name}</span>
+                </label>
+            </div>
+            <p id="{}" class="help is-danger">{message}</p>
+        "#,
+            self.id, self.name_id, self.message_id, self.name_id, self.message_id
+        ))
+    }
+}
+
+impl Edit for Upload {
+    type Task = EditUpload<ShareValue<Upload>>;
+
+    fn edit(value: Option<Self>) -> Self::Task {
+        EditUpload::new(ShareValue::new(value))
+    }
+}
+
+impl<S> EditShared<S> for Upload
+where
+    S: ShareRead<Value = Self> + Send + Sync,
+{
+    type Task = EditUpload<S>;
+
+    fn edit_shared(share: S) -> Self::Task {
+        EditUpload::new(share)
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::task::{TaskValue, Value, WithLabel};
+
+/// Checks a value, given the name of what is being checked, returning a readable message if it is
+/// not valid.
+type Validator<T> = Box<dyn Fn(&T, &str) -> Result<(), String> + Send + Sync>;
+
+/// Wraps a task, turning its value into a [`TaskValue::Error`] if it does not pass all validators.
+/// The message of the first failing validator is shown below the task.
+pub struct Validate<T: Value> {
+    id: Uuid,
+    task: T,
+    label: Option<String>,
+    validators: Vec<Validator<T::Output>>,
+}
+
+impl<T> Validate<T>
+where
+    T: Value,
+{
+    pub fn new(task: T) -> Self {
+        Validate {
+            id: Uuid::new_v4(),
+            task,
+            label: None,
+            validators: Vec::new(),
+        }
+    }
+
+    /// Adds a custom validator, whose error is reported as is.
+    pub fn with<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&T::Output) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.validators
+            .push(Box::new(move |value, _| validator(value)));
+        self
+    }
+
+    /// Requires the value to be at least `min`.
+    pub fn min(mut self, min: T::Output) -> Self
+    where
+        T::Output: PartialOrd + Display + Send + Sync + 'static,
+    {
+        self.validators
+            .push(Box::new(move |value, name| match *value < min {
+                true => Err(format!("{name} must be at least {min}")),
+                false => Ok(()),
+            }));
+        self
+    }
+
+    /// Requires the value to be at most `max`.
+    pub fn max(mut self, max: T::Output) -> Self
+    where
+        T::Output: PartialOrd + Display + Send + Sync + 'static,
+    {
+        self.validators
+            .push(Box::new(move |value, name| match *value > max {
+                true => Err(format!("{name} must be at most {max}")),
+                false => Ok(()),
+            }));
+        self
+    }
+
+    /// Requires the length of the value to be within the given bounds.
+    pub fn length(mut self, min: Option<usize>, max: Option<usize>) -> Self
+    where
+        T::Output: Length,
+    {
+        self.validators.push(Box::new(move |value, name| {
+            let length = value.length();
+            match (min, max) {
+                (Some(min), _) if length < min => {
+                    Err(format!("{name} must have a length of at least {min}"))
+                }
+                (_, Some(max)) if length > max => {
+                    Err(format!("{name} must have a length of at most {max}"))
+                }
+                _ => Ok(()),
+            }
+        }));
+        self
+    }
+
+    /// Requires the entire value to match the regular expression `pattern`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is not a valid regular expression.
+    pub fn regex(mut self, pattern: &str) -> Self
+    where
+        T::Output: AsRef<str>,
+    {
+        let regex = Regex::new(&format!("^(?:{pattern})$")).expect("invalid regular expression");
+        let pattern = pattern.to_owned();
+        self.validators.push(Box::new(move |value, name| {
+            match regex.is_match(value.as_ref()) {
+                true => Ok(()),
+                false => Err(format!("{name} must match `{pattern}`")),
+            }
+        }));
+        self
+    }
+
+    /// The message of the first validator rejecting `value`, if any.
+    fn check(&self, value: &TaskValue<T::Output>) -> Option<String> {
+        let name = self.label.as_deref().unwrap_or("Value");
+        match value {
+            TaskValue::Stable(value) | TaskValue::Unstable(value) => self
+                .validators
+                .iter()
+                .find_map(|validator| validator(value, name).err()),
+            TaskValue::Error(_) | TaskValue::Empty => None,
+        }
+    }
+
+    /// Shows the current validation message below the task. If the value is rejected while the
+    /// task marks the `input` that was changed as valid, it is marked as invalid instead.
+    async fn update_message(&self, feedback: Feedback, input: Option<Uuid>) -> Feedback
+    where
+        T: Sync,
+        T::Output: Send,
+    {
+        let message = self.check(&self.task.value().await);
+        let feedback = match (&message, input) {
+            (Some(_), Some(id)) if matches!(feedback.get(&id), Some(Change::Valid { .. })) => {
+                feedback
+                    .merged_with(Feedback::from(Change::Invalid { id }))
+                    .unwrap()
+            }
+            _ => feedback,
+        };
+        feedback
+            .merged_with(Feedback::from(Change::ReplaceContent {
+                id: self.id,
+                html: Html(message.unwrap_or_default()),
+            }))
+            .unwrap()
+    }
+}
+
+impl<T> WithLabel for Validate<T>
+where
+    T: Value + WithLabel,
+{
+    fn with_label(mut self, label: String) -> Self {
+        self.task = self.task.with_label(label.clone());
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<T> Value for Validate<T>
+where
+    T: Value + Send + Sync,
+    T::Output: Send,
+{
+    type Output = T::Output;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        let value = self.task.value().await;
+        match self.check(&value) {
+            None => value,
+            Some(message) => TaskValue::Error(message),
+        }
+    }
+}
+
+#[async_trait]
+impl<T> Handler for Validate<T>
+where
+    T: Value + Handler + Send + Sync,
+    T::Output: Send,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        let input = match &event {
+            Event::Update { id, .. } => Some(*id),
+            _ => None,
+        };
+        let feedback = self.task.on_event(event).await;
+        if feedback.is_empty() && feedback.shares().is_empty() {
+            return feedback;
+        }
+
+        self.update_message(feedback, input).await
+    }
+}
+
+#[async_trait]
+impl<T> Refresh for Validate<T>
+where
+    T: Value + Refresh + Send + Sync,
+    T::Output: Send,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        let feedback = self.task.refresh(ids).await;
+        if feedback.is_empty() {
+            return feedback;
+        }
+
+        self.update_message(feedback, None).await
+    }
+}
+
+#[async_trait]
+impl<T> ToHtml for Validate<T>
+where
+    T: Value + ToHtml + Send + Sync,
+    T::Output: Send,
+{
+    async fn to_html(&self) -> Html {
+        let task = self.task.to_html().await;
+        let message = self.check(&self.task.value().await).unwrap_or_default();
+        Html(format!(
+            r#"{task}<p id="{}" class="help is-danger">{message}</p>"#,
+            self.id
+        ))
+    }
+}
+
+/// Values that have a length, which can be validated with [`Validate::length`].
+pub trait Length {
+    fn length(&self) -> usize;
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
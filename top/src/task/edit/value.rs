@@ -1,5 +1,4 @@
 use std::collections::BTreeSet;
-use std::fmt::Display;
 
 use async_trait::async_trait;
 use uuid::Uuid;
@@ -7,14 +6,15 @@ use uuid::Uuid;
 use crate::html::event::{Change, Event, Feedback};
 use crate::html::{Handler, Html, Refresh, ToHtml};
 use crate::share::{ShareRead, ShareUpdate, ShareWrite};
-use crate::task::edit::form::{FromForm, IntoForm};
-use crate::task::{TaskValue, Value};
+use crate::task::edit::form::{FromForm, IntoForm, Widget};
+use crate::task::{TaskValue, Value, WithLabel};
 
 #[derive(Clone, Debug)]
 pub struct EditValue<S> {
     id: Uuid,
     share: S,
     label: Option<String>,
+    widget: Option<Widget>,
 }
 
 impl<S> EditValue<S> {
@@ -23,6 +23,7 @@ impl<S> EditValue<S> {
             id: Uuid::new_v4(),
             share,
             label: None,
+            widget: None,
         }
     }
 
@@ -30,6 +31,32 @@ impl<S> EditValue<S> {
         self.label = Some(label);
         self
     }
+
+    pub fn with_widget(mut self, widget: Widget) -> Self {
+        self.widget = Some(widget);
+        self
+    }
+}
+
+impl<S> EditValue<S>
+where
+    S: ShareRead,
+    S::Value: IntoForm,
+{
+    /// The value as it is sent to the form element, or `None` if it must never be sent to the
+    /// client, such as when it is edited as a password.
+    fn form_value(&self, value: &S::Value) -> Option<String> {
+        match self.widget {
+            Some(Widget::Password) => None,
+            _ => value.form_value(),
+        }
+    }
+}
+
+impl<S> WithLabel for EditValue<S> {
+    fn with_label(self, label: String) -> Self {
+        EditValue::with_label(self, label)
+    }
 }
 
 #[async_trait]
@@ -74,17 +101,16 @@ where
 impl<S> Refresh for EditValue<S>
 where
     S: ShareRead + ShareUpdate + Send + Sync,
-    // TODO: Don't use Display, use IntoForm
-    S::Value: Display + Send + Sync,
+    S::Value: IntoForm + Send + Sync,
 {
     async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
         if self.share.updated(ids) {
             match self.share.read().as_ref() {
                 TaskValue::Stable(value) | TaskValue::Unstable(value) => {
-                    Feedback::from(Change::UpdateValue {
-                        id: self.id,
-                        value: value.to_string(),
-                    })
+                    match self.form_value(value) {
+                        Some(value) => Feedback::from(Change::UpdateValue { id: self.id, value }),
+                        None => Feedback::from(Change::Valid { id: self.id }),
+                    }
                 }
                 TaskValue::Error(_) => Feedback::from(Change::Invalid { id: self.id }),
                 TaskValue::Empty => Feedback::from(Change::UpdateValue {
@@ -105,10 +131,11 @@ where
     S::Value: IntoForm + Send,
 {
     async fn to_html(&self) -> Html {
-        S::Value::into_form(
-            self.share.read().as_ref(),
-            &self.id,
-            self.label.as_deref().unwrap_or_default(),
-        )
+        let value = self.share.read();
+        let label = self.label.as_deref().unwrap_or_default();
+        match self.widget {
+            None => S::Value::into_form(value.as_ref(), &self.id, label),
+            Some(widget) => S::Value::into_widget(value.as_ref(), &self.id, label, widget),
+        }
     }
 }
@@ -6,72 +6,181 @@ use uuid::Uuid;
 
 use crate::html::event::{Change, Event, Feedback};
 use crate::html::{Handler, Html, Refresh, ToHtml};
-use crate::share::{ShareChildren, ShareRead, ShareUpdate, ShareWrite};
+use crate::share::{ShareChildren, ShareCreate, ShareRead, ShareUpdate};
 use crate::task::edit::edit_shared;
 use crate::task::edit::edit_shared::EditShared;
-use crate::task::{TaskValue, Value};
+use crate::task::{OptionExt, TaskValue, Value, WithLabel};
 
+/// Editor for lists, which shows an editor for each element. Elements can be added, removed and
+/// moved, and the value of the list is an error as long as its length is out of bounds.
 #[derive(Clone, Debug)]
-pub struct EditVec<S, T> {
+pub struct EditVec<S, T>
+where
+    T: Value,
+{
     container_id: Uuid,
     elements_id: Uuid,
     add_id: Uuid,
+    message_id: Uuid,
     rows: Vec<Row>,
     share: S,
+    child_ids: Vec<Uuid>,
     tasks: Vec<T>,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    template: Option<T::Output>,
+    label: Option<String>,
 }
 
 impl<S, T> EditVec<S, T>
 where
     S: ShareChildren,
-    S::Child: ShareRead<Value = T::Output> + Clone,
+    S::Child: ShareRead<Value = T::Output> + ShareUpdate + Clone,
     T: Value,
     T::Output: EditShared<S::Child, Task = T>,
 {
     pub fn new(share: S) -> Self {
-        let tasks: Vec<T> = share.children().iter().cloned().map(edit_shared).collect();
+        let children = share.children().clone();
+        let child_ids = children.iter().map(ShareUpdate::id).collect();
+        let tasks: Vec<T> = children.into_iter().map(edit_shared).collect();
         let rows = tasks.iter().map(|_| Row::new()).collect();
         EditVec {
             container_id: Uuid::new_v4(),
             elements_id: Uuid::new_v4(),
             add_id: Uuid::new_v4(),
+            message_id: Uuid::new_v4(),
             rows,
             share,
+            child_ids,
             tasks,
+            min_len: None,
+            max_len: None,
+            template: None,
+            label: None,
+        }
+    }
+}
+
+impl<S, T> EditVec<S, T>
+where
+    T: Value,
+{
+    /// Requires the list to have at least `min_len` elements, and disables removing elements below
+    /// that length.
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = Some(min_len);
+        self
+    }
+
+    /// Requires the list to have at most `max_len` elements, and disables adding elements beyond
+    /// that length.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Fills new elements with `template`, instead of leaving them empty.
+    pub fn with_template(mut self, template: T::Output) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    fn can_add(&self, len: usize) -> bool {
+        !matches!(self.max_len, Some(max_len) if len >= max_len)
+    }
+
+    fn can_remove(&self, len: usize) -> bool {
+        !matches!(self.min_len, Some(min_len) if len <= min_len)
+    }
+
+    /// The message explaining why a list of `len` elements is not allowed, if it is not.
+    fn check(&self, len: usize) -> Option<String> {
+        match (self.min_len, self.max_len) {
+            (Some(min_len), _) if len < min_len => {
+                Some(format!("Enter at least {}", elements(min_len)))
+            }
+            (_, Some(max_len)) if len > max_len => {
+                Some(format!("Enter at most {}", elements(max_len)))
+            }
+            _ => None,
         }
     }
 }
 
+impl<S, T> EditVec<S, T>
+where
+    S: ShareChildren + ShareUpdate,
+    T: Value,
+{
+    /// Swaps the rows at `index` and `other`, by swapping their shares and tasks and moving the
+    /// existing elements, instead of rendering them again.
+    fn swap(&mut self, index: usize, other: usize) -> Feedback {
+        self.share.children().swap(index, other);
+        self.child_ids.swap(index, other);
+        self.tasks.swap(index, other);
+        self.rows.swap(index, other);
+        let feedback = Feedback::from(Change::Swap {
+            id: self.rows[index].container_id,
+            other: self.rows[other].container_id,
+        });
+        feedback
+            .merged_with(Feedback::update_share(self.share.id()))
+            .unwrap()
+    }
+}
+
+impl<S, T> WithLabel for EditVec<S, T>
+where
+    T: Value,
+{
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
 #[async_trait]
 impl<S, T> Value for EditVec<S, T>
 where
-    S: ShareRead + Send + Sync,
-    S::Value: Clone,
-    T: Send + Sync,
+    S: ShareRead<Value = Vec<T::Output>> + Send + Sync,
+    T: Value + Send + Sync,
+    T::Output: Clone + Send + Sync,
 {
     type Output = S::Value;
 
     async fn value(&self) -> TaskValue<Self::Output> {
-        self.share.read().as_ref().clone()
+        let value = self.share.read().as_ref().clone();
+        match &value {
+            TaskValue::Stable(elements) | TaskValue::Unstable(elements) => {
+                match self.check(elements.len()) {
+                    Some(message) => TaskValue::Error(message),
+                    None => value,
+                }
+            }
+            TaskValue::Error(_) | TaskValue::Empty => value,
+        }
     }
 }
 
 #[async_trait]
 impl<S, T> Handler for EditVec<S, T>
 where
-    S: ShareChildren + Send,
-    S::Child: ShareWrite,
+    S: ShareChildren + ShareUpdate + Send,
+    S::Child: ShareCreate<Value = T::Output>,
     T: Value + Handler + Send + Sync,
-    T::Output: Clone,
+    T::Output: Clone + Send + Sync,
 {
     async fn on_event(&mut self, event: Event) -> Feedback {
         match event {
             Event::Press { id } if id == self.add_id => {
-                // Add a new row
-                self.share
-                    .children()
-                    .push(<S::Child as ShareWrite>::create(TaskValue::Empty));
-                Feedback::new()
+                // Add a new row, filled with the template if there is one
+                let mut children = self.share.children();
+                if !self.can_add(children.len()) {
+                    return Feedback::new();
+                }
+                let value = self.template.clone().into_unstable();
+                children.push(<S::Child as ShareCreate>::create(value));
+                Feedback::update_share(self.share.id())
             }
             Event::Press { id } if self.rows.iter().any(|row| row.remove_id == id) => {
                 // Remove an existing row
@@ -80,9 +189,29 @@ where
                     .iter()
                     .position(|row| row.remove_id == id)
                     .unwrap();
-                self.share.children().remove(index);
-
-                Feedback::new()
+                let mut children = self.share.children();
+                if !self.can_remove(children.len()) {
+                    return Feedback::new();
+                }
+                children.remove(index);
+                Feedback::update_share(self.share.id())
+            }
+            Event::Press { id } if self.rows.iter().any(|row| row.up_id == id) => {
+                // Move an existing row up, unless it is the first one
+                let index = self.rows.iter().position(|row| row.up_id == id).unwrap();
+                match index.checked_sub(1) {
+                    Some(other) => self.swap(index, other),
+                    None => Feedback::new(),
+                }
+            }
+            Event::Press { id } if self.rows.iter().any(|row| row.down_id == id) => {
+                // Move an existing row down, unless it is the last one
+                let index = self.rows.iter().position(|row| row.down_id == id).unwrap();
+                if index + 1 < self.rows.len() {
+                    self.swap(index, index + 1)
+                } else {
+                    Feedback::new()
+                }
             }
             _ => future::join_all(
                 self.tasks
@@ -100,77 +229,201 @@ where
 impl<S, T> Refresh for EditVec<S, T>
 where
     S: ShareChildren + ShareUpdate + Send + Sync,
-    S::Child: ShareRead + Clone,
+    S::Child: ShareRead + ShareUpdate + Clone + Send,
     <S::Child as ShareRead>::Value: EditShared<S::Child, Task = T>,
-    T: ToHtml + Send + Sync,
+    T: Value + Refresh + ToHtml + Send + Sync,
+    T::Output: Send + Sync,
 {
     async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
-        if self.share.updated(&ids) {
-            self.tasks = self
-                .share
-                .children()
-                .iter()
-                .cloned()
-                .map(edit_shared)
-                .collect();
-            self.rows = self.tasks.iter().map(|_| Row::new()).collect();
-            Feedback::from(Change::Replace {
-                id: self.container_id,
-                html: self.to_html().await,
-            })
-        } else {
-            Feedback::new()
+        if !self.share.updated(ids) {
+            return Feedback::new();
+        }
+
+        let children = self.share.children().clone();
+        let child_ids: Vec<Uuid> = children.iter().map(ShareUpdate::id).collect();
+        let retained = match retained(&self.child_ids, &child_ids) {
+            Some(retained) => retained,
+            None => {
+                // The elements were reordered, so start over
+                self.tasks = children.into_iter().map(edit_shared).collect();
+                self.rows = self.tasks.iter().map(|_| Row::new()).collect();
+                self.child_ids = child_ids;
+                return Feedback::from(Change::Replace {
+                    id: self.container_id,
+                    html: self.to_html().await,
+                });
+            }
+        };
+
+        // Remove the rows of elements that are gone, and refresh the others
+        let (old_len, len) = (self.child_ids.len(), child_ids.len());
+        let mut feedback = Vec::new();
+        let mut tasks = Vec::new();
+        let mut rows = Vec::new();
+        let old = self.tasks.drain(..).zip(self.rows.drain(..));
+        for ((mut task, row), retained) in old.zip(retained) {
+            if retained {
+                feedback.push(task.refresh(ids).await);
+                tasks.push(task);
+                rows.push(row);
+            } else {
+                feedback.push(Feedback::from(Change::Remove {
+                    id: row.container_id,
+                }));
+            }
+        }
+
+        // Update the buttons of the rows that are kept, if removing them is now (dis)allowed
+        if self.can_remove(old_len) != self.can_remove(len) {
+            for row in &rows {
+                feedback.push(Feedback::from(Change::Replace {
+                    id: row.remove_id,
+                    html: remove_button(row, self.can_remove(len)),
+                }));
+            }
+        }
+
+        // Add rows for the new elements
+        for child in children.into_iter().skip(tasks.len()) {
+            let task: T = edit_shared(child);
+            let row = Row::new();
+            feedback.push(Feedback::from(Change::AppendContent {
+                id: self.elements_id,
+                html: row_html(task.to_html().await, &row, self.can_remove(len)),
+            }));
+            tasks.push(task);
+            rows.push(row);
         }
+
+        if self.can_add(old_len) != self.can_add(len) {
+            feedback.push(Feedback::from(Change::Replace {
+                id: self.add_id,
+                html: add_button(self.add_id, self.can_add(len)),
+            }));
+        }
+        if self.check(old_len) != self.check(len) {
+            feedback.push(Feedback::from(Change::ReplaceContent {
+                id: self.message_id,
+                html: Html(self.check(len).unwrap_or_default()),
+            }));
+        }
+
+        self.tasks = tasks;
+        self.rows = rows;
+        self.child_ids = child_ids;
+        feedback.into_iter().collect()
     }
 }
 
 #[async_trait]
 impl<S, T> ToHtml for EditVec<S, T>
 where
-    S: Send + Sync,
-    T: ToHtml + Send + Sync,
+    S: ShareChildren + Send + Sync,
+    T: Value + ToHtml + Send + Sync,
+    T::Output: Send + Sync,
 {
     async fn to_html(&self) -> Html {
+        let len = self.share.children().len();
         let children: Html = future::join_all(self.tasks.iter().map(ToHtml::to_html))
             .await
             .into_iter()
             .zip(&self.rows)
-            .map(|(task, row)| {
-                Html(format!(
-                    r#"
-                        <div id={}>
-                            {task}
-                            <button id="{}" class="button" type="button" onclick="press(this)">-</button>
-                        </div>
-                    "#,
-                    row.container_id, row.remove_id
-                ))
-            })
+            .map(|(task, row)| row_html(task, row, self.can_remove(len)))
             .collect();
 
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+
         Html(format!(
             r#"
                 <div id="{}" class="column">
+                    {label}
                     <div id="{}" class="column">{children}</div>
-                    <button id="{}" class="button" type="button" onclick="press(this)">+</button>
+                    {}
+                    <p id="{}" class="help is-danger">{}</p>
                 </div>
             "#,
-            self.container_id, self.elements_id, self.add_id
+            self.container_id,
+            self.elements_id,
+            add_button(self.add_id, self.can_add(len)),
+            self.message_id,
+            self.check(len).unwrap_or_default()
         ))
     }
 }
 
+/// The row showing `task`, with buttons to move and remove it.
+fn row_html(task: Html, row: &Row, can_remove: bool) -> Html {
+    Html(format!(
+        r#"
+            <div id={}>
+                {task}
+                <button id="{}" class="button" type="button" onclick="press(this)">↑</button>
+                <button id="{}" class="button" type="button" onclick="press(this)">↓</button>
+                {}
+            </div>
+        "#,
+        row.container_id,
+        row.up_id,
+        row.down_id,
+        remove_button(row, can_remove)
+    ))
+}
+
+fn remove_button(row: &Row, can_remove: bool) -> Html {
+    let disabled = if can_remove { "" } else { "disabled" };
+    Html(format!(
+        r#"<button id="{}" class="button" type="button" onclick="press(this)" {disabled}>-</button>"#,
+        row.remove_id
+    ))
+}
+
+fn add_button(id: Uuid, can_add: bool) -> Html {
+    let disabled = if can_add { "" } else { "disabled" };
+    Html(format!(
+        r#"<button id="{id}" class="button" type="button" onclick="press(this)" {disabled}>+</button>"#
+    ))
+}
+
+/// Compares the ids of the children of a collection before and after a change, giving for each of
+/// the old children whether it is retained. This is `None` if the change is more than removing
+/// children and appending new ones, such as when children are reordered.
+pub(crate) fn retained(old: &[Uuid], new: &[Uuid]) -> Option<Vec<bool>> {
+    let new_ids: BTreeSet<&Uuid> = new.iter().collect();
+    let retained: Vec<bool> = old.iter().map(|id| new_ids.contains(id)).collect();
+    let kept: Vec<&Uuid> = old.iter().filter(|id| new_ids.contains(id)).collect();
+    // The retained children must come first, in the same order as before
+    let in_order = kept.iter().copied().eq(new.iter().take(kept.len()));
+    in_order.then_some(retained)
+}
+
+/// Ids of the elements wrapping a single element of a collection.
 #[derive(Clone, Debug)]
-struct Row {
-    container_id: Uuid,
-    remove_id: Uuid,
+pub(crate) struct Row {
+    pub(crate) container_id: Uuid,
+    pub(crate) remove_id: Uuid,
+    pub(crate) up_id: Uuid,
+    pub(crate) down_id: Uuid,
 }
 
 impl Row {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Row {
             container_id: Uuid::new_v4(),
             remove_id: Uuid::new_v4(),
+            up_id: Uuid::new_v4(),
+            down_id: Uuid::new_v4(),
         }
     }
 }
+
+/// Describes a number of elements, such as `1 element` or `2 elements`.
+fn elements(count: usize) -> String {
+    match count {
+        1 => String::from("1 element"),
+        count => format!("{count} elements"),
+    }
+}
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 
 use crate::html::{Handler, Refresh, ToHtml};
 
+pub mod choice;
 pub mod edit;
 pub mod parallel;
 pub mod sequential;
@@ -11,6 +12,11 @@ pub trait Task: Value + Handler + Refresh + ToHtml {}
 
 impl<T> Task for T where T: Value + Handler + Refresh + ToHtml {}
 
+/// Tasks that can be given a label describing their content.
+pub trait WithLabel {
+    fn with_label(self, label: String) -> Self;
+}
+
 #[async_trait]
 pub trait Value {
     type Output;
@@ -134,6 +140,19 @@ impl<T> TaskValue<T> {
     }
 }
 
+/// Nests `bindings` into the pattern of the value produced by chaining [`TaskValue::and`], such as
+/// `((((), a), b), c)`.
+macro_rules! nested {
+    ($pattern:tt;) => {
+        $pattern
+    };
+    ($pattern:tt; $binding:ident $($bindings:ident)*) => {
+        nested!(($pattern, $binding); $($bindings)*)
+    };
+}
+
+pub(crate) use nested;
+
 impl<T> IntoIterator for TaskValue<T> {
     type Item = T;
     type IntoIter = <Option<T> as IntoIterator>::IntoIter;
@@ -7,12 +7,13 @@ use uuid::Uuid;
 use crate::html::event::{Change, Event, Feedback};
 use crate::html::{Handler, Html, Refresh, ToHtml};
 use crate::share::{ShareRead, ShareUpdate};
-use crate::task::{TaskValue, Value};
+use crate::task::{TaskValue, Value, WithLabel};
 
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ViewDisplay<S> {
     id: Uuid,
     share: S,
+    label: Option<String>,
 }
 
 impl<S> ViewDisplay<S> {
@@ -20,10 +21,18 @@ impl<S> ViewDisplay<S> {
         ViewDisplay {
             id: Uuid::new_v4(),
             share,
+            label: None,
         }
     }
 }
 
+impl<S> WithLabel for ViewDisplay<S> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
 #[async_trait]
 impl<S> Value for ViewDisplay<S>
 where
@@ -78,6 +87,11 @@ where
             TaskValue::Error(error) => format!(r#"<span style="color: red;">{error}</span>"#),
             TaskValue::Empty => String::new(),
         };
-        Html(format!(r#"<div id="{}">{string}</div>"#, self.id))
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+        Html(format!(r#"<div id="{}">{label}{string}</div>"#, self.id))
     }
 }
@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareRead, ShareUpdate, ShareValue};
+use crate::task::edit::format_duration;
+use crate::task::view::{View, ViewShared};
+use crate::task::{TaskValue, Value, WithLabel};
+
+/// Viewer for durations, which shows them as hours, minutes and seconds, since [`Duration`] does
+/// not implement [`Display`](std::fmt::Display).
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ViewDuration<S> {
+    id: Uuid,
+    share: S,
+    label: Option<String>,
+}
+
+impl<S> ViewDuration<S> {
+    pub fn new(share: S) -> Self {
+        ViewDuration {
+            id: Uuid::new_v4(),
+            share,
+            label: None,
+        }
+    }
+}
+
+impl<S> WithLabel for ViewDuration<S> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S> Value for ViewDuration<S>
+where
+    S: ShareRead<Value = Duration> + Send + Sync,
+{
+    type Output = Duration;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        self.share.read().as_ref().clone()
+    }
+}
+
+#[async_trait]
+impl<S> Handler for ViewDuration<S>
+where
+    S: Send,
+{
+    async fn on_event(&mut self, _event: Event) -> Feedback {
+        Feedback::new()
+    }
+}
+
+#[async_trait]
+impl<S> Refresh for ViewDuration<S>
+where
+    S: ShareRead<Value = Duration> + ShareUpdate + Send + Sync,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        if self.share.updated(ids) {
+            Feedback::from(Change::Replace {
+                id: self.id,
+                html: self.to_html().await,
+            })
+        } else {
+            Feedback::new()
+        }
+    }
+}
+
+#[async_trait]
+impl<S> ToHtml for ViewDuration<S>
+where
+    S: ShareRead<Value = Duration> + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let value = self.share.read();
+        let string = match value.as_ref() {
+            TaskValue::Stable(value) | TaskValue::Unstable(value) => format_duration(value),
+            TaskValue::Error(error) => format!(r#"<span style="color: red;">{error}</span>"#),
+            TaskValue::Empty => String::new(),
+        };
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+        Html(format!(r#"<div id="{}">{label}{string}</div>"#, self.id))
+    }
+}
+
+impl View for Duration {
+    type Task = ViewDuration<ShareValue<Duration>>;
+
+    fn view(self) -> Self::Task {
+        ViewDuration::new(ShareValue::new(Some(self)))
+    }
+}
+
+impl<S> ViewShared<S> for Duration
+where
+    S: ShareRead<Value = Self> + Send + Sync,
+{
+    type Task = ViewDuration<S>;
+
+    fn view_shared(share: S) -> Self::Task {
+        ViewDuration::new(share)
+    }
+}
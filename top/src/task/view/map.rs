@@ -0,0 +1,151 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use futures::future;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareChildren, ShareRead, ShareUpdate};
+use crate::task::view::view_shared;
+use crate::task::view::view_shared::ViewShared;
+use crate::task::{TaskValue, Value, WithLabel};
+
+/// Viewer for maps, which shows the key next to the value of each entry.
+#[derive(Clone, Debug)]
+pub struct ViewMap<S, K, V> {
+    container_id: Uuid,
+    share: S,
+    tasks: Vec<(K, V)>,
+    label: Option<String>,
+}
+
+impl<S, K, V, SK, SV> ViewMap<S, K, V>
+where
+    S: ShareChildren<Child = (SK, SV)>,
+    SK: ShareRead<Value = K::Output> + Clone,
+    SV: ShareRead<Value = V::Output> + Clone,
+    K: Value,
+    K::Output: ViewShared<SK, Task = K>,
+    V: Value,
+    V::Output: ViewShared<SV, Task = V>,
+{
+    pub fn new(share: S) -> Self {
+        ViewMap {
+            container_id: Uuid::new_v4(),
+            tasks: Self::tasks(&share),
+            share,
+            label: None,
+        }
+    }
+
+    fn tasks(share: &S) -> Vec<(K, V)> {
+        share
+            .children()
+            .iter()
+            .cloned()
+            .map(|(key, value)| (view_shared(key), view_shared(value)))
+            .collect()
+    }
+}
+
+impl<S, K, V> WithLabel for ViewMap<S, K, V> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S, K, V> Value for ViewMap<S, K, V>
+where
+    S: ShareRead + Send + Sync,
+    S::Value: Clone,
+    K: Send + Sync,
+    V: Send + Sync,
+{
+    type Output = S::Value;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        self.share.read().as_ref().clone()
+    }
+}
+
+#[async_trait]
+impl<S, K, V> Handler for ViewMap<S, K, V>
+where
+    S: Send,
+    K: Handler + Send,
+    V: Handler + Send,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        future::join_all(self.tasks.iter_mut().map(|(key, value)| async {
+            key.on_event(event.clone())
+                .await
+                .merged_with(value.on_event(event.clone()).await)
+                .unwrap()
+        }))
+        .await
+        .into_iter()
+        .collect()
+    }
+}
+
+#[async_trait]
+impl<S, K, V, SK, SV> Refresh for ViewMap<S, K, V>
+where
+    S: ShareChildren<Child = (SK, SV)> + ShareUpdate + Send + Sync,
+    SK: ShareRead<Value = K::Output> + Clone,
+    SV: ShareRead<Value = V::Output> + Clone,
+    K: Value + ToHtml + Send + Sync,
+    K::Output: ViewShared<SK, Task = K>,
+    V: Value + ToHtml + Send + Sync,
+    V::Output: ViewShared<SV, Task = V>,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        if self.share.updated(ids) {
+            self.tasks = Self::tasks(&self.share);
+            Feedback::from(Change::Replace {
+                id: self.container_id,
+                html: self.to_html().await,
+            })
+        } else {
+            Feedback::new()
+        }
+    }
+}
+
+#[async_trait]
+impl<S, K, V> ToHtml for ViewMap<S, K, V>
+where
+    S: Send + Sync,
+    K: ToHtml + Send + Sync,
+    V: ToHtml + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let children: Html = future::join_all(
+            self.tasks
+                .iter()
+                .map(|(key, value)| future::join(key.to_html(), value.to_html())),
+        )
+        .await
+        .into_iter()
+        .map(|(key, value)| {
+            Html(format!(
+                r#"<div class="columns"><div class="column">{key}</div><div class="column">{value}</div></div>"#
+            ))
+        })
+        .collect();
+
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+
+        Html(format!(
+            r#"<div id="{}" class="column">{label}{children}</div>"#,
+            self.container_id
+        ))
+    }
+}
@@ -1,11 +1,19 @@
 pub use display::ViewDisplay;
+pub use duration::ViewDuration;
+pub use map::ViewMap;
+pub use option::ViewOption;
+pub use tuple::ViewTuple;
 pub use vec::ViewVec;
+pub use view::View;
+pub use view_shared::ViewShared;
 
 use crate::share::ShareRead;
-use crate::task::view::view::View;
-use crate::task::view::view_shared::ViewShared;
 
 mod display;
+mod duration;
+mod map;
+mod option;
+mod tuple;
 mod vec;
 mod view;
 mod view_shared;
@@ -0,0 +1,120 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareRead, ShareUpdate};
+use crate::task::view::View;
+use crate::task::{TaskValue, Value, WithLabel};
+
+/// Viewer for optional values, which shows nothing if there is no value.
+#[derive(Clone, Debug)]
+pub struct ViewOption<S, T> {
+    id: Uuid,
+    share: S,
+    task: Option<T>,
+    label: Option<String>,
+}
+
+impl<S, T> ViewOption<S, T>
+where
+    T: Value,
+    T::Output: View<Task = T>,
+{
+    pub fn new(share: S, value: Option<Option<T::Output>>) -> Self {
+        ViewOption {
+            id: Uuid::new_v4(),
+            share,
+            task: value.flatten().map(View::view),
+            label: None,
+        }
+    }
+}
+
+impl<S, T> WithLabel for ViewOption<S, T> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+#[async_trait]
+impl<S, T> Value for ViewOption<S, T>
+where
+    S: Send + Sync,
+    T: Value + Send + Sync,
+{
+    type Output = Option<T::Output>;
+
+    async fn value(&self) -> TaskValue<Self::Output> {
+        match &self.task {
+            None => TaskValue::Unstable(None),
+            Some(task) => match task.value().await {
+                TaskValue::Stable(value) => TaskValue::Stable(Some(value)),
+                TaskValue::Unstable(value) => TaskValue::Unstable(Some(value)),
+                TaskValue::Error(error) => TaskValue::Error(error),
+                TaskValue::Empty => TaskValue::Empty,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> Handler for ViewOption<S, T>
+where
+    S: Send,
+    T: Handler + Send,
+{
+    async fn on_event(&mut self, event: Event) -> Feedback {
+        match &mut self.task {
+            None => Feedback::new(),
+            Some(task) => task.on_event(event).await,
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> Refresh for ViewOption<S, T>
+where
+    S: ShareRead<Value = Option<T::Output>> + ShareUpdate + Send + Sync,
+    T: Value + Refresh + ToHtml + Send + Sync,
+    T::Output: View<Task = T> + Clone,
+{
+    async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+        if self.share.updated(ids) {
+            let value: Option<_> = self.share.read().as_ref().clone().into();
+            self.task = value.flatten().map(View::view);
+            Feedback::from(Change::Replace {
+                id: self.id,
+                html: self.to_html().await,
+            })
+        } else {
+            match &mut self.task {
+                None => Feedback::new(),
+                Some(task) => task.refresh(ids).await,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> ToHtml for ViewOption<S, T>
+where
+    S: Send + Sync,
+    T: ToHtml + Send + Sync,
+{
+    async fn to_html(&self) -> Html {
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+        let task = match &self.task {
+            None => Html::default(),
+            Some(task) => task.to_html().await,
+        };
+        Html(format!(r#"<div id="{}">{label}{task}</div>"#, self.id))
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::html::event::{Change, Event, Feedback};
+use crate::html::{Handler, Html, Refresh, ToHtml};
+use crate::share::{ShareRead, ShareUpdate, ShareValue};
+use crate::task::view::{View, ViewShared};
+use crate::task::{nested, TaskValue, Value, WithLabel};
+
+/// Viewer for tuples, which shows the viewers of its components side by side. `T` is the tuple of
+/// the tasks viewing each component.
+#[derive(Clone, Debug)]
+pub struct ViewTuple<S, T> {
+    id: Uuid,
+    share: S,
+    tasks: Option<T>,
+    label: Option<String>,
+}
+
+impl<S, T> WithLabel for ViewTuple<S, T> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+macro_rules! impl_view_tuple {
+    ($(($($index:tt $task:ident $value:ident),+)),+) => {
+        $(
+            impl<S, $($task),+> ViewTuple<S, ($($task,)+)>
+            where
+                $($task: Value, $task::Output: View<Task = $task>,)+
+            {
+                pub fn new(share: S, value: Option<($($task::Output,)+)>) -> Self {
+                    ViewTuple {
+                        id: Uuid::new_v4(),
+                        share,
+                        tasks: Self::tasks(value),
+                        label: None,
+                    }
+                }
+
+                fn tasks(value: Option<($($task::Output,)+)>) -> Option<($($task,)+)> {
+                    value.map(|($($value,)+)| ($($value.view(),)+))
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> Value for ViewTuple<S, ($($task,)+)>
+            where
+                S: Send + Sync,
+                $($task: Value + Send + Sync, $task::Output: Send,)+
+            {
+                type Output = ($($task::Output,)+);
+
+                async fn value(&self) -> TaskValue<Self::Output> {
+                    let tasks = match &self.tasks {
+                        Some(tasks) => tasks,
+                        None => return TaskValue::Empty,
+                    };
+                    let value = TaskValue::Stable(())$(.and(tasks.$index.value().await))+;
+                    match value {
+                        TaskValue::Stable(nested!(() ; $($value)+)) => {
+                            TaskValue::Stable(($($value,)+))
+                        }
+                        TaskValue::Unstable(nested!(() ; $($value)+)) => {
+                            TaskValue::Unstable(($($value,)+))
+                        }
+                        TaskValue::Error(error) => TaskValue::Error(error),
+                        TaskValue::Empty => TaskValue::Empty,
+                    }
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> Handler for ViewTuple<S, ($($task,)+)>
+            where
+                S: Send,
+                $($task: Handler + Send,)+
+            {
+                async fn on_event(&mut self, event: Event) -> Feedback {
+                    let tasks = match &mut self.tasks {
+                        Some(tasks) => tasks,
+                        None => return Feedback::new(),
+                    };
+                    let feedback = Feedback::new();
+                    $(
+                        let component = tasks.$index.on_event(event.clone()).await;
+                        let feedback = feedback.merged_with(component).unwrap();
+                    )+
+                    feedback
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> Refresh for ViewTuple<S, ($($task,)+)>
+            where
+                S: ShareRead<Value = ($($task::Output,)+)> + ShareUpdate + Send + Sync,
+                $(
+                    $task: Value + Refresh + ToHtml + Send + Sync,
+                    $task::Output: View<Task = $task> + Clone,
+                )+
+            {
+                async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
+                    if self.share.updated(ids) {
+                        let value = self.share.read().as_ref().clone();
+                        self.tasks = Self::tasks(value.into());
+                        Feedback::from(Change::Replace {
+                            id: self.id,
+                            html: self.to_html().await,
+                        })
+                    } else {
+                        let tasks = match &mut self.tasks {
+                            Some(tasks) => tasks,
+                            None => return Feedback::new(),
+                        };
+                        let feedback = Feedback::new();
+                        $(
+                            let component = tasks.$index.refresh(ids).await;
+                            let feedback = feedback.merged_with(component).unwrap();
+                        )+
+                        feedback
+                    }
+                }
+            }
+
+            #[async_trait]
+            impl<S, $($task),+> ToHtml for ViewTuple<S, ($($task,)+)>
+            where
+                S: Send + Sync,
+                $($task: ToHtml + Send + Sync,)+
+            {
+                async fn to_html(&self) -> Html {
+                    let label = self
+                        .label
+                        .as_ref()
+                        .map(|label| format!(r#"<label class="label">{label}</label>"#))
+                        .unwrap_or_default();
+                    let components: Html = match &self.tasks {
+                        Some(tasks) => vec![
+                            $(Html(format!(
+                                r#"<div class="column">{}</div>"#,
+                                tasks.$index.to_html().await
+                            )),)+
+                        ]
+                        .into_iter()
+                        .collect(),
+                        None => Html::default(),
+                    };
+                    Html(format!(
+                        r#"<div id="{}">{label}<div class="columns">{components}</div></div>"#,
+                        self.id
+                    ))
+                }
+            }
+
+            impl<$($task),+> View for ($($task,)+)
+            where
+                $($task: View + Send, $task::Task: Send + Sync,)+
+            {
+                type Task = ViewTuple<ShareValue<Self>, ($($task::Task,)+)>;
+
+                fn view(self) -> Self::Task {
+                    Self::Task::new(ShareValue::new(None), Some(self))
+                }
+            }
+
+            impl<S, $($task),+> ViewShared<S> for ($($task,)+)
+            where
+                S: ShareRead<Value = Self> + Send + Sync,
+                $($task: View + Clone + Send, $task::Task: Send + Sync,)+
+            {
+                type Task = ViewTuple<S, ($($task::Task,)+)>;
+
+                fn view_shared(share: S) -> Self::Task {
+                    let value = share.read().as_ref().clone().into();
+                    Self::Task::new(share, value)
+                }
+            }
+        )+
+    };
+}
+
+impl_view_tuple!(
+    (0 T0 a),
+    (0 T0 a, 1 T1 b),
+    (0 T0 a, 1 T1 b, 2 T2 c),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i, 9 T9 j),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i, 9 T9 j, 10 T10 k),
+    (0 T0 a, 1 T1 b, 2 T2 c, 3 T3 d, 4 T4 e, 5 T5 f, 6 T6 g, 7 T7 h, 8 T8 i, 9 T9 j, 10 T10 k, 11 T11 l)
+);
@@ -7,34 +7,51 @@ use uuid::Uuid;
 use crate::html::event::{Change, Event, Feedback};
 use crate::html::{Handler, Html, Refresh, ToHtml};
 use crate::share::{ShareChildren, ShareRead, ShareUpdate, ShareWrite};
+use crate::task::edit::retained;
 use crate::task::view::view_shared;
 use crate::task::view::view_shared::ViewShared;
-use crate::task::{TaskValue, Value};
+use crate::task::{TaskValue, Value, WithLabel};
 
 #[derive(Clone, Debug)]
 pub struct ViewVec<S, T> {
     container_id: Uuid,
+    row_ids: Vec<Uuid>,
     share: S,
+    child_ids: Vec<Uuid>,
     tasks: Vec<T>,
+    label: Option<String>,
 }
 
 impl<S, T> ViewVec<S, T>
 where
     S: ShareChildren,
-    S::Child: ShareRead<Value = T::Output> + Clone,
+    S::Child: ShareRead<Value = T::Output> + ShareUpdate + Clone,
     T: Value,
     T::Output: ViewShared<S::Child, Task = T>,
 {
     pub fn new(share: S) -> Self {
-        let tasks: Vec<T> = share.children().iter().cloned().map(view_shared).collect();
+        let children = share.children().clone();
+        let child_ids = children.iter().map(ShareUpdate::id).collect();
+        let tasks: Vec<T> = children.into_iter().map(view_shared).collect();
+        let row_ids = tasks.iter().map(|_| Uuid::new_v4()).collect();
         ViewVec {
             container_id: Uuid::new_v4(),
+            row_ids,
             share,
+            child_ids,
             tasks,
+            label: None,
         }
     }
 }
 
+impl<S, T> WithLabel for ViewVec<S, T> {
+    fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
 #[async_trait]
 impl<S, T> Value for ViewVec<S, T>
 where
@@ -75,26 +92,65 @@ where
 impl<S, T> Refresh for ViewVec<S, T>
 where
     S: ShareChildren + ShareUpdate + Send + Sync,
-    S::Child: ShareRead + Clone,
+    S::Child: ShareRead + ShareUpdate + Clone + Send,
     <S::Child as ShareRead>::Value: ViewShared<S::Child, Task = T>,
-    T: ToHtml + Send + Sync,
+    T: Refresh + ToHtml + Send + Sync,
 {
     async fn refresh(&mut self, ids: &BTreeSet<Uuid>) -> Feedback {
-        if self.share.updated(&ids) {
-            self.tasks = self
-                .share
-                .children()
-                .iter()
-                .cloned()
-                .map(view_shared)
-                .collect();
-            Feedback::from(Change::Replace {
+        if !self.share.updated(ids) {
+            return Feedback::new();
+        }
+
+        let children = self.share.children().clone();
+        let child_ids: Vec<Uuid> = children.iter().map(ShareUpdate::id).collect();
+        let retained = match retained(&self.child_ids, &child_ids) {
+            Some(retained) => retained,
+            None => {
+                // The elements were reordered, so start over
+                self.tasks = children.into_iter().map(view_shared).collect();
+                self.row_ids = self.tasks.iter().map(|_| Uuid::new_v4()).collect();
+                self.child_ids = child_ids;
+                return Feedback::from(Change::Replace {
+                    id: self.container_id,
+                    html: self.to_html().await,
+                });
+            }
+        };
+
+        // Remove the rows of elements that are gone, and refresh the others
+        let mut feedback = Vec::new();
+        let mut tasks = Vec::new();
+        let mut row_ids = Vec::new();
+        let old = self.tasks.drain(..).zip(self.row_ids.drain(..));
+        for ((mut task, row_id), retained) in old.zip(retained) {
+            if retained {
+                feedback.push(task.refresh(ids).await);
+                tasks.push(task);
+                row_ids.push(row_id);
+            } else {
+                feedback.push(Feedback::from(Change::Remove { id: row_id }));
+            }
+        }
+
+        // Add rows for the new elements
+        for child in children.into_iter().skip(tasks.len()) {
+            let task: T = view_shared(child);
+            let row_id = Uuid::new_v4();
+            feedback.push(Feedback::from(Change::AppendContent {
                 id: self.container_id,
-                html: self.to_html().await,
-            })
-        } else {
-            Feedback::new()
+                html: Html(format!(
+                    r#"<div id="{row_id}">{}</div>"#,
+                    task.to_html().await
+                )),
+            }));
+            tasks.push(task);
+            row_ids.push(row_id);
         }
+
+        self.tasks = tasks;
+        self.row_ids = row_ids;
+        self.child_ids = child_ids;
+        feedback.into_iter().collect()
     }
 }
 
@@ -108,10 +164,18 @@ where
         let children: Html = future::join_all(self.tasks.iter().map(ToHtml::to_html))
             .await
             .into_iter()
+            .zip(&self.row_ids)
+            .map(|(task, row_id)| Html(format!(r#"<div id="{row_id}">{task}</div>"#)))
             .collect();
 
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(r#"<label class="label">{label}</label>"#))
+            .unwrap_or_default();
+
         Html(format!(
-            r#"<div id="{}" class="column">{children}</div>"#,
+            r#"<div id="{}" class="column">{label}{children}</div>"#,
             self.container_id
         ))
     }
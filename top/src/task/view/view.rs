@@ -1,7 +1,12 @@
-use crate::share::{ShareValue, ShareVec};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+pub use top_derive::View;
+
+use crate::share::{ShareMap, ShareValue, ShareVec};
 use crate::task::view::display::ViewDisplay;
 use crate::task::view::view_shared::ViewShared;
-use crate::task::view::ViewVec;
+use crate::task::view::{ViewMap, ViewOption, ViewVec};
 use crate::task::Value;
 
 pub trait View: Sized {
@@ -56,3 +61,43 @@ where
         ViewVec::new(ShareVec::new(Some(self)))
     }
 }
+
+impl<K, V> View for BTreeMap<K, V>
+where
+    K: ViewShared<ShareValue<K>> + Ord + Clone + Send,
+    K::Task: Send + Sync,
+    V: ViewShared<ShareValue<V>> + Clone + Send,
+    V::Task: Send + Sync,
+{
+    type Task = ViewMap<ShareMap<Self, ShareValue<K>, ShareValue<V>>, K::Task, V::Task>;
+
+    fn view(self) -> Self::Task {
+        ViewMap::new(ShareMap::new(Some(self)))
+    }
+}
+
+impl<K, V> View for HashMap<K, V>
+where
+    K: ViewShared<ShareValue<K>> + Eq + Hash + Clone + Send,
+    K::Task: Send + Sync,
+    V: ViewShared<ShareValue<V>> + Clone + Send,
+    V::Task: Send + Sync,
+{
+    type Task = ViewMap<ShareMap<Self, ShareValue<K>, ShareValue<V>>, K::Task, V::Task>;
+
+    fn view(self) -> Self::Task {
+        ViewMap::new(ShareMap::new(Some(self)))
+    }
+}
+
+impl<T> View for Option<T>
+where
+    T: View + Send,
+    T::Task: Send + Sync,
+{
+    type Task = ViewOption<ShareValue<Option<T>>, T::Task>;
+
+    fn view(self) -> Self::Task {
+        ViewOption::new(ShareValue::new(None), Some(self))
+    }
+}
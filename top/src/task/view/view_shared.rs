@@ -1,6 +1,9 @@
-use crate::share::{ShareChildren, ShareRead};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::share::{ShareChildren, ShareRead, ShareUpdate};
 use crate::task::view::display::ViewDisplay;
-use crate::task::view::ViewVec;
+use crate::task::view::{View, ViewMap, ViewOption, ViewVec};
 use crate::task::Value;
 
 pub trait ViewShared<S>: Sized {
@@ -52,7 +55,7 @@ where
     T: ViewShared<S::Child> + Clone,
     T::Task: Send + Sync,
     S: ShareChildren + ShareRead<Value = Vec<T>> + Send + Sync,
-    S::Child: ShareRead<Value = T> + Clone,
+    S::Child: ShareRead<Value = T> + ShareUpdate + Clone,
 {
     type Task = ViewVec<S, T::Task>;
 
@@ -60,3 +63,51 @@ where
         ViewVec::new(share)
     }
 }
+
+impl<S, K, V, SK, SV> ViewShared<S> for BTreeMap<K, V>
+where
+    K: ViewShared<SK> + Ord + Clone,
+    K::Task: Send + Sync,
+    V: ViewShared<SV> + Clone,
+    V::Task: Send + Sync,
+    S: ShareChildren<Child = (SK, SV)> + ShareRead<Value = Self> + ShareUpdate + Send + Sync,
+    SK: ShareRead<Value = K> + Clone,
+    SV: ShareRead<Value = V> + Clone,
+{
+    type Task = ViewMap<S, K::Task, V::Task>;
+
+    fn view_shared(share: S) -> Self::Task {
+        ViewMap::new(share)
+    }
+}
+
+impl<S, K, V, SK, SV> ViewShared<S> for HashMap<K, V>
+where
+    K: ViewShared<SK> + Eq + Hash + Clone,
+    K::Task: Send + Sync,
+    V: ViewShared<SV> + Clone,
+    V::Task: Send + Sync,
+    S: ShareChildren<Child = (SK, SV)> + ShareRead<Value = Self> + ShareUpdate + Send + Sync,
+    SK: ShareRead<Value = K> + Clone,
+    SV: ShareRead<Value = V> + Clone,
+{
+    type Task = ViewMap<S, K::Task, V::Task>;
+
+    fn view_shared(share: S) -> Self::Task {
+        ViewMap::new(share)
+    }
+}
+
+impl<S, T> ViewShared<S> for Option<T>
+where
+    T: View + Clone + Send,
+    T::Task: Send + Sync,
+    S: ShareRead<Value = Option<T>> + Send + Sync,
+{
+    type Task = ViewOption<S, T::Task>;
+
+    fn view_shared(share: S) -> Self::Task {
+        let value = share.read().as_ref().clone().into();
+        ViewOption::new(share, value)
+    }
+}
@@ -0,0 +1,113 @@
+use top::html::ToHtml;
+use top::task::edit::Edit;
+use top::task::view::View;
+use top::task::{TaskValue, Value};
+
+#[derive(Clone, Debug, Default, PartialEq, Edit, View)]
+struct Person {
+    #[top(label = "Full name", length(min = 1, max = 40))]
+    name: String,
+    #[top(min = 0, max = 150)]
+    age: u8,
+    #[top(widget = "password")]
+    password: String,
+    #[top(readonly)]
+    id: u32,
+    #[top(skip, default = 7)]
+    version: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Edit, View)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rectangle { width: f64, height: f64 },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Edit, View)]
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+fn person() -> Person {
+    Person {
+        name: "Alice".to_owned(),
+        age: 30,
+        password: "secret".to_owned(),
+        id: 1,
+        version: 3,
+    }
+}
+
+#[tokio::test]
+async fn struct_editor() {
+    let editor = Person::edit(Some(person()));
+    let html = editor.to_html().await.0;
+    assert!(html.contains("Full name"));
+    assert!(html.contains("Alice"));
+    assert!(!html.contains("secret"));
+    assert_eq!(editor.value().await, TaskValue::Unstable(person()));
+
+    let editor = Person::edit(None);
+    editor.to_html().await;
+    assert!(!matches!(
+        editor.value().await,
+        TaskValue::Stable(_) | TaskValue::Unstable(_)
+    ));
+}
+
+#[tokio::test]
+async fn struct_viewer() {
+    let viewer = person().view();
+    let html = viewer.to_html().await.0;
+    assert!(html.contains("Full name"));
+    assert!(html.contains("Alice"));
+    assert_eq!(viewer.value().await, TaskValue::Unstable(person()));
+}
+
+#[tokio::test]
+async fn enum_editor() {
+    let shape = Shape::Rectangle {
+        width: 2.0,
+        height: 3.0,
+    };
+    let editor = Shape::edit(Some(shape.clone()));
+    let html = editor.to_html().await.0;
+    assert!(html.contains("Rectangle"));
+    assert_eq!(editor.value().await, TaskValue::Unstable(shape));
+
+    let editor = Shape::edit(Some(Shape::Point));
+    assert_eq!(editor.value().await, TaskValue::Unstable(Shape::Point));
+
+    let editor = Shape::edit(None);
+    editor.to_html().await;
+    assert_eq!(editor.value().await, TaskValue::Empty);
+}
+
+#[tokio::test]
+async fn enum_viewer() {
+    let viewer = Shape::Circle(1.5).view();
+    let html = viewer.to_html().await.0;
+    assert!(html.contains("1.5"));
+    assert_eq!(
+        viewer.value().await,
+        TaskValue::Unstable(Shape::Circle(1.5))
+    );
+}
+
+#[tokio::test]
+async fn generic_editor() {
+    let pair = Pair {
+        first: "one".to_owned(),
+        second: 2u64,
+    };
+    let editor = Pair::edit(Some(pair.clone()));
+    let html = editor.to_html().await.0;
+    assert!(html.contains("one"));
+    assert_eq!(editor.value().await, TaskValue::Unstable(pair.clone()));
+
+    let viewer = pair.clone().view();
+    viewer.to_html().await;
+    assert_eq!(viewer.value().await, TaskValue::Unstable(pair));
+}
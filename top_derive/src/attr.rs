@@ -0,0 +1,150 @@
+use syn::parse::ParseStream;
+use syn::{parenthesized, Attribute, Error, Expr, Ident, LitStr, Path, Result, Token};
+
+/// Options set through `#[top(...)]` attributes on a field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// Label shown next to the field, instead of its humanized name.
+    pub label: Option<String>,
+    /// Leave the field out of the user interface, keeping its initial value.
+    pub skip: bool,
+    /// Initial value of the field when there is no value yet.
+    pub default: Option<Expr>,
+    /// Show the field's value without allowing the user to change it.
+    pub readonly: bool,
+    /// Alternative input element for the field.
+    pub widget: Option<Widget>,
+    /// Function checking the field's value.
+    pub validate: Option<Path>,
+    /// Smallest value allowed.
+    pub min: Option<Expr>,
+    /// Largest value allowed.
+    pub max: Option<Expr>,
+    /// Bounds on the length of the value.
+    pub length: Option<Length>,
+    /// Regular expression the value must match.
+    pub regex: Option<LitStr>,
+}
+
+/// Bounds set through `length(min = .., max = ..)`.
+#[derive(Default)]
+pub struct Length {
+    pub min: Option<Expr>,
+    pub max: Option<Expr>,
+}
+
+pub enum Widget {
+    TextArea,
+    Password,
+    Slider,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut field_attrs = FieldAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("top")) {
+            attr.parse_args_with(|input: ParseStream| {
+                while !input.is_empty() {
+                    field_attrs.parse_arg(input)?;
+                    if !input.is_empty() {
+                        input.parse::<Token![,]>()?;
+                    }
+                }
+                Ok(())
+            })?;
+            if field_attrs.skip && field_attrs.validates() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "skipped fields cannot be validated",
+                ));
+            }
+        }
+        Ok(field_attrs)
+    }
+
+    /// Whether the field's value is checked by any validators.
+    pub fn validates(&self) -> bool {
+        self.validate.is_some()
+            || self.min.is_some()
+            || self.max.is_some()
+            || self.length.is_some()
+            || self.regex.is_some()
+    }
+
+    fn parse_arg(&mut self, input: ParseStream) -> Result<()> {
+        let ident: Ident = input.parse()?;
+        match ident.to_string().as_str() {
+            "label" => {
+                input.parse::<Token![=]>()?;
+                self.label = Some(input.parse::<LitStr>()?.value());
+            }
+            "skip" => self.skip = true,
+            "default" => {
+                input.parse::<Token![=]>()?;
+                self.default = Some(input.parse()?);
+            }
+            "readonly" => self.readonly = true,
+            "widget" => {
+                input.parse::<Token![=]>()?;
+                let widget: LitStr = input.parse()?;
+                self.widget = Some(match widget.value().as_str() {
+                    "textarea" => Widget::TextArea,
+                    "password" => Widget::Password,
+                    "slider" => Widget::Slider,
+                    _ => {
+                        return Err(Error::new(
+                            widget.span(),
+                            "expected `textarea`, `password` or `slider`",
+                        ))
+                    }
+                });
+            }
+            "validate" => {
+                input.parse::<Token![=]>()?;
+                self.validate = Some(input.parse()?);
+            }
+            "min" => {
+                input.parse::<Token![=]>()?;
+                self.min = Some(input.parse()?);
+            }
+            "max" => {
+                input.parse::<Token![=]>()?;
+                self.max = Some(input.parse()?);
+            }
+            "length" => {
+                let content;
+                parenthesized!(content in input);
+                self.length = Some(Length::parse(&content)?);
+            }
+            "regex" => {
+                input.parse::<Token![=]>()?;
+                let regex: LitStr = input.parse()?;
+                if let Err(error) = regex::Regex::new(&regex.value()) {
+                    return Err(Error::new(regex.span(), error));
+                }
+                self.regex = Some(regex);
+            }
+            _ => return Err(Error::new(ident.span(), "unknown attribute")),
+        }
+        Ok(())
+    }
+}
+
+impl Length {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut length = Length::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match ident.to_string().as_str() {
+                "min" => length.min = Some(input.parse()?),
+                "max" => length.max = Some(input.parse()?),
+                _ => return Err(Error::new(ident.span(), "expected `min` or `max`")),
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(length)
+    }
+}
@@ -0,0 +1,782 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Error, Expr, Fields, Result, Type};
+
+use crate::attr::{FieldAttrs, Widget};
+use crate::generics::Generic;
+use crate::record::Record;
+
+pub fn impl_edit(ast: DeriveInput) -> Result<TokenStream> {
+    match &ast.data {
+        Data::Struct(data_struct) => impl_edit_struct(&ast, data_struct),
+        Data::Enum(data_enum) => impl_edit_enum(&ast, data_enum),
+        Data::Union(_) => Err(Error::new_spanned(&ast, "unions are not supported")),
+    }
+}
+
+fn impl_edit_struct(ast: &DeriveInput, data_struct: &DataStruct) -> Result<TokenStream> {
+    let ident = &ast.ident;
+    let vis = &ast.vis;
+    let editor_ident = format_ident!("{ident}Editor");
+    let generic = Generic::new(&ast.generics);
+    let share = generic.share();
+    let this = generic.apply(ident, None);
+    let editor = generic.apply(&editor_ident, Some(share.to_token_stream()));
+    let params = generic.params(true);
+
+    let record = Record::new(quote!(#ident), &data_struct.fields)?;
+    let predicates = generic.predicates();
+    let field_bounds = field_bounds(&record, &generic);
+    let bounds = quote!(#predicates #(#field_bounds,)*);
+    let pattern = record.pattern();
+    let field_types = generic.without_lifetimes(record.types());
+    let slots = slot_types(&record);
+    let slot_types = generic.without_lifetimes(slots.clone());
+    let task_types = generic.without_lifetimes(task_types(&record));
+    let skipped_types = generic.without_lifetimes(skipped_types(&record));
+    let tasks = quote!(self.tasks);
+    let start_tasks = start_tasks(&record);
+    let enter_tasks = enter_tasks(&record);
+    let value = record.value(&tasks);
+    let on_event = record.on_event(&tasks);
+    let refresh = record.refresh(&tasks);
+    let to_html = record.to_html(&tasks);
+
+    let doc = format!("Editor for [`{ident}`], generated by `#[derive(Edit)]`.");
+    let editor_struct = quote! {
+        #[doc = #doc]
+        #vis struct #editor_ident #params
+        where
+            #bounds
+        {
+            id: ::top::__private::Uuid,
+            share: #share,
+            written: bool,
+            label: Option<String>,
+            tasks: (#(#slots,)*),
+        }
+
+        impl #params #editor
+        where
+            #bounds
+        {
+            fn new(share: #share, value: Option<#this>) -> Self {
+                #editor_ident {
+                    id: ::top::__private::Uuid::new_v4(),
+                    share,
+                    written: false,
+                    label: None,
+                    tasks: Self::tasks(value),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn tasks(value: Option<#this>) -> (#(#slots,)*) {
+                match value {
+                    Some(#pattern) => #start_tasks,
+                    None => #enter_tasks,
+                }
+            }
+        }
+    };
+
+    let value_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::task::Value for #editor
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#field_types: Send,)*
+            #(#slot_types: Send + Sync,)*
+            #(#skipped_types: Clone,)*
+        {
+            type Output = #this;
+
+            async fn value(&self) -> ::top::task::TaskValue<Self::Output> {
+                #value
+            }
+        }
+    };
+
+    let handler_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Handler for #editor
+        where
+            #bounds
+            #share: ::top::share::ShareWrite<Value = #this> + ::top::share::ShareUpdate + Send + Sync,
+            #editor: ::top::task::Value<Output = #this>,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::Handler + Send + Sync,)*
+        {
+            async fn on_event(
+                &mut self,
+                event: ::top::html::event::Event,
+            ) -> ::top::html::event::Feedback {
+                let feedback = #on_event;
+                if feedback.shares().is_empty() {
+                    return feedback;
+                }
+
+                // One of the fields changed, so the record as a whole did as well
+                let value = ::top::task::Value::value(self).await;
+                ::top::share::ShareWrite::write(&self.share, value);
+                self.written = true;
+                feedback.combined_with(::top::html::event::Feedback::update_share(
+                    ::top::share::ShareUpdate::id(&self.share),
+                ))
+            }
+        }
+    };
+
+    let refresh_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Refresh for #editor
+        where
+            #bounds
+            #share: ::top::share::ShareRead<Value = #this> + ::top::share::ShareUpdate + Send + Sync,
+            #this: Clone,
+            #editor: ::top::html::ToHtml,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::Refresh + Send + Sync,)*
+        {
+            async fn refresh(
+                &mut self,
+                ids: &::std::collections::BTreeSet<::top::__private::Uuid>,
+            ) -> ::top::html::event::Feedback {
+                let written = ::std::mem::replace(&mut self.written, false);
+                if ::top::share::ShareUpdate::updated(&self.share, ids) && !written {
+                    // Someone else changed the record, start over with its new value
+                    let value = ::top::share::ShareRead::read(&self.share).as_ref().clone();
+                    self.tasks = Self::tasks(value.into());
+                    ::top::html::event::Feedback::from(::top::html::event::Change::Replace {
+                        id: self.id,
+                        html: ::top::html::ToHtml::to_html(self).await,
+                    })
+                } else {
+                    #refresh
+                }
+            }
+        }
+    };
+
+    let label = label_html();
+    let to_html_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::ToHtml for #editor
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::ToHtml + Send + Sync,)*
+        {
+            async fn to_html(&self) -> ::top::html::Html {
+                let id = self.id;
+                let label = #label;
+                let fields = #to_html;
+                ::top::html::Html(format!(r#"<div id="{id}">{label}{fields}</div>"#))
+            }
+        }
+    };
+
+    let with_label_impl = impl_with_label(&params, &editor, &bounds);
+    let edit_impls = impl_edit_traits(&generic, ident, &editor_ident, &bounds);
+
+    Ok(quote! {
+        #editor_struct
+        #value_impl
+        #handler_impl
+        #refresh_impl
+        #to_html_impl
+        #with_label_impl
+        #edit_impls
+    })
+}
+
+fn impl_edit_enum(ast: &DeriveInput, data_enum: &DataEnum) -> Result<TokenStream> {
+    let ident = &ast.ident;
+    let vis = &ast.vis;
+    let editor_ident = format_ident!("{ident}Editor");
+    let variant_ident = format_ident!("{ident}EditorVariant");
+    let generic = Generic::new(&ast.generics);
+    let share = generic.share();
+    let this = generic.apply(ident, None);
+    let editor = generic.apply(&editor_ident, Some(share.to_token_stream()));
+    let variant = generic.apply(&variant_ident, None);
+    let params = generic.params(true);
+    let variant_params = generic.params(false);
+
+    let variant_idents: Vec<_> = data_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect();
+    let variant_names: Vec<_> = variant_idents
+        .iter()
+        .map(|variant| variant.to_string())
+        .collect();
+    let records: Vec<_> = data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+            Record::new(quote!(#ident::#variant_ident), &variant.fields)
+        })
+        .collect::<Result<_>>()?;
+    let predicates = generic.predicates();
+    let field_bounds: Vec<_> = records
+        .iter()
+        .flat_map(|record| field_bounds(record, &generic))
+        .collect();
+    let bounds = quote!(#predicates #(#field_bounds,)*);
+    let unit_only = data_enum
+        .variants
+        .iter()
+        .all(|variant| matches!(variant.fields, Fields::Unit));
+
+    let patterns: Vec<_> = records.iter().map(Record::pattern).collect();
+    let field_types = generic.without_lifetimes(records.iter().flat_map(Record::types).collect());
+    let slot_types = generic.without_lifetimes(records.iter().flat_map(slot_types).collect());
+    let task_types = generic.without_lifetimes(records.iter().flat_map(task_types).collect());
+    let skipped_types = generic.without_lifetimes(records.iter().flat_map(skipped_types).collect());
+    let variant_slot_types: Vec<_> = records
+        .iter()
+        .map(|record| {
+            let slot_types = self::slot_types(record);
+            quote!((#(#slot_types,)*))
+        })
+        .collect();
+    let tasks = quote!(tasks);
+    let start_tasks: Vec<_> = records.iter().map(start_tasks).collect();
+    let enter_tasks: Vec<_> = records.iter().map(enter_tasks).collect();
+    let values: Vec<_> = records.iter().map(|record| record.value(&tasks)).collect();
+    let on_events: Vec<_> = records
+        .iter()
+        .map(|record| record.on_event(&tasks))
+        .collect();
+    let refreshes: Vec<_> = records
+        .iter()
+        .map(|record| record.refresh(&tasks))
+        .collect();
+    let to_htmls: Vec<_> = records
+        .iter()
+        .map(|record| record.to_html(&tasks))
+        .collect();
+
+    let doc = format!("Editor for [`{ident}`], generated by `#[derive(Edit)]`.");
+    let variant_doc = format!("Field editors for the chosen variant of [`{ident}`].");
+    let editor_struct = quote! {
+        #[doc = #doc]
+        #vis struct #editor_ident #params
+        where
+            #bounds
+        {
+            id: ::top::__private::Uuid,
+            choice_id: ::top::__private::Uuid,
+            fields_id: ::top::__private::Uuid,
+            share: #share,
+            written: bool,
+            label: Option<String>,
+            variant: Option<#variant>,
+        }
+
+        #[doc = #variant_doc]
+        #[doc(hidden)]
+        #[allow(clippy::large_enum_variant)]
+        #vis enum #variant_ident #variant_params
+        where
+            #bounds
+        {
+            #(#variant_idents(#variant_slot_types),)*
+        }
+
+        impl #params #editor
+        where
+            #bounds
+        {
+            fn new(share: #share, value: Option<#this>) -> Self {
+                #editor_ident {
+                    id: ::top::__private::Uuid::new_v4(),
+                    choice_id: ::top::__private::Uuid::new_v4(),
+                    fields_id: ::top::__private::Uuid::new_v4(),
+                    share,
+                    written: false,
+                    label: None,
+                    variant: Self::variant(value),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn variant(value: Option<#this>) -> Option<#variant> {
+                match value {
+                    #(Some(#patterns) => Some(#variant_ident::#variant_idents(#start_tasks)),)*
+                    None => None,
+                }
+            }
+        }
+    };
+
+    let variant_to_html_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #variant_params ::top::html::ToHtml for #variant
+        where
+            #bounds
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::ToHtml + Send + Sync,)*
+        {
+            #[allow(unused_variables)]
+            async fn to_html(&self) -> ::top::html::Html {
+                match self {
+                    #(#variant_ident::#variant_idents(tasks) => #to_htmls,)*
+                }
+            }
+        }
+    };
+
+    let value_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::task::Value for #editor
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#field_types: Send,)*
+            #(#slot_types: Send + Sync,)*
+            #(#skipped_types: Clone,)*
+        {
+            type Output = #this;
+
+            #[allow(unused_variables)]
+            async fn value(&self) -> ::top::task::TaskValue<Self::Output> {
+                match &self.variant {
+                    #(Some(#variant_ident::#variant_idents(tasks)) => #values,)*
+                    None => ::top::task::TaskValue::Empty,
+                }
+            }
+        }
+    };
+
+    // Swap out the field editors when another variant is chosen, unless there are none
+    let replace_fields = (!unit_only).then(|| {
+        quote! {
+            let html = match &self.variant {
+                Some(variant) => ::top::html::ToHtml::to_html(variant).await,
+                None => ::top::html::Html::default(),
+            };
+            let feedback = feedback.combined_with(::top::html::event::Feedback::from(
+                ::top::html::event::Change::ReplaceContent {
+                    id: self.fields_id,
+                    html,
+                },
+            ));
+        }
+    });
+
+    let handler_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Handler for #editor
+        where
+            #bounds
+            #share: ::top::share::ShareWrite<Value = #this> + ::top::share::ShareUpdate + Send + Sync,
+            #editor: ::top::task::Value<Output = #this>,
+            #variant: ::top::html::ToHtml,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::Handler + Send + Sync,)*
+        {
+            #[allow(unused_variables)]
+            async fn on_event(
+                &mut self,
+                event: ::top::html::event::Event,
+            ) -> ::top::html::event::Feedback {
+                let feedback = match event {
+                    ::top::html::event::Event::Update { id, value } if id == self.choice_id => {
+                        // Start over with empty fields for the chosen variant
+                        self.variant = match value.as_str() {
+                            #(#variant_names => Some(#variant_ident::#variant_idents(#enter_tasks)),)*
+                            _ => {
+                                return ::top::html::event::Feedback::from(
+                                    ::top::html::event::Change::Invalid { id },
+                                );
+                            }
+                        };
+                        let feedback = ::top::html::event::Feedback::from(
+                            ::top::html::event::Change::Valid { id },
+                        );
+                        #replace_fields
+                        feedback
+                    }
+                    event => {
+                        let feedback = match &mut self.variant {
+                            #(Some(#variant_ident::#variant_idents(tasks)) => #on_events,)*
+                            None => ::top::html::event::Feedback::new(),
+                        };
+                        if feedback.shares().is_empty() {
+                            return feedback;
+                        }
+                        feedback
+                    }
+                };
+
+                // The variant or one of its fields changed, so the value as a whole did as well
+                let value = ::top::task::Value::value(self).await;
+                ::top::share::ShareWrite::write(&self.share, value);
+                self.written = true;
+                feedback.combined_with(::top::html::event::Feedback::update_share(
+                    ::top::share::ShareUpdate::id(&self.share),
+                ))
+            }
+        }
+    };
+
+    let refresh_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Refresh for #editor
+        where
+            #bounds
+            #share: ::top::share::ShareRead<Value = #this> + ::top::share::ShareUpdate + Send + Sync,
+            #this: Clone,
+            #editor: ::top::html::ToHtml,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::Refresh + Send + Sync,)*
+        {
+            #[allow(unused_variables)]
+            async fn refresh(
+                &mut self,
+                ids: &::std::collections::BTreeSet<::top::__private::Uuid>,
+            ) -> ::top::html::event::Feedback {
+                let written = ::std::mem::replace(&mut self.written, false);
+                if ::top::share::ShareUpdate::updated(&self.share, ids) && !written {
+                    // Someone else changed the value, start over with its new variant
+                    let value = ::top::share::ShareRead::read(&self.share).as_ref().clone();
+                    self.variant = Self::variant(value.into());
+                    ::top::html::event::Feedback::from(::top::html::event::Change::Replace {
+                        id: self.id,
+                        html: ::top::html::ToHtml::to_html(self).await,
+                    })
+                } else {
+                    match &mut self.variant {
+                        #(Some(#variant_ident::#variant_idents(tasks)) => #refreshes,)*
+                        None => ::top::html::event::Feedback::new(),
+                    }
+                }
+            }
+        }
+    };
+
+    let fields = (!unit_only).then(|| {
+        quote! {
+            let fields_id = self.fields_id;
+            let fields = match &self.variant {
+                Some(variant) => ::top::html::ToHtml::to_html(variant).await,
+                None => ::top::html::Html::default(),
+            };
+            let select = format!(r#"{select}<div id="{fields_id}">{fields}</div>"#);
+        }
+    });
+
+    let label = label_html();
+    let to_html_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::ToHtml for #editor
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#slot_types: Send + Sync,)*
+            #variant: ::top::html::ToHtml,
+        {
+            async fn to_html(&self) -> ::top::html::Html {
+                let chosen = match &self.variant {
+                    #(Some(#variant_ident::#variant_idents(_)) => Some(#variant_names),)*
+                    None => None,
+                };
+                let placeholder = match chosen {
+                    Some(_) => "",
+                    None => r#"<option value="" disabled selected hidden></option>"#,
+                };
+                let options: String = [#(#variant_names),*]
+                    .iter()
+                    .map(|name| {
+                        let selected = if chosen == Some(*name) { "selected" } else { "" };
+                        format!(r#"<option value="{name}" {selected}>{name}</option>"#)
+                    })
+                    .collect();
+                let select = format!(
+                    r#"<div class="select">
+                        <select id="{}" onchange="update(this)">{placeholder}{options}</select>
+                    </div>"#,
+                    self.choice_id
+                );
+                let id = self.id;
+                let label = #label;
+                #fields
+                ::top::html::Html(format!(r#"<div id="{id}">{label}{select}</div>"#))
+            }
+        }
+    };
+
+    let with_label_impl = impl_with_label(&params, &editor, &bounds);
+    let edit_impls = impl_edit_traits(&generic, ident, &editor_ident, &bounds);
+
+    Ok(quote! {
+        #editor_struct
+        #variant_to_html_impl
+        #value_impl
+        #handler_impl
+        #refresh_impl
+        #to_html_impl
+        #with_label_impl
+        #edit_impls
+    })
+}
+
+/// Implements `WithLabel` for the generated editor or viewer `task`, which needs a `label` field.
+pub fn impl_with_label(
+    params: &TokenStream,
+    task: &TokenStream,
+    bounds: &TokenStream,
+) -> TokenStream {
+    quote! {
+        impl #params ::top::task::WithLabel for #task
+        where
+            #bounds
+        {
+            fn with_label(mut self, label: ::std::string::String) -> Self {
+                self.label = Some(label);
+                self
+            }
+        }
+    }
+}
+
+/// Renders the `label` field of a generated editor or viewer, if it is set.
+///
+/// Like the id, the label is rendered before awaiting the fields, so `self` is not held across an
+/// await. Otherwise the future is only `Send` if the task is `Sync` for any lifetime of the type.
+pub fn label_html() -> TokenStream {
+    quote! {
+        match &self.label {
+            Some(label) => format!(r#"<label class="label">{label}</label>"#),
+            None => String::new(),
+        }
+    }
+}
+
+/// Implements `Edit` and `EditShared` for `ident` through its generated editor.
+fn impl_edit_traits(
+    generic: &Generic,
+    ident: &Ident,
+    editor_ident: &Ident,
+    bounds: &TokenStream,
+) -> TokenStream {
+    let share = generic.share();
+    let this = generic.apply(ident, None);
+    let params = generic.params(false);
+    let shared_params = generic.params(true);
+    let editor = generic.apply(editor_ident, Some(share.to_token_stream()));
+    let owned_editor = generic.apply(editor_ident, Some(quote!(::top::share::ShareValue<#this>)));
+
+    quote! {
+        impl #params ::top::task::edit::Edit for #this
+        where
+            #bounds
+            #owned_editor: ::top::task::Value<Output = #this>,
+        {
+            type Task = #owned_editor;
+
+            fn edit(value: Option<Self>) -> Self::Task {
+                #editor_ident::new(::top::share::ShareValue::new(None), value)
+            }
+        }
+
+        impl #shared_params ::top::task::edit::EditShared<#share> for #this
+        where
+            #bounds
+            #share: ::top::share::ShareRead<Value = #this> + Send + Sync,
+            #this: Clone,
+            #editor: ::top::task::Value<Output = #this>,
+        {
+            type Task = #editor;
+
+            fn edit_shared(share: #share) -> Self::Task {
+                let value = ::top::share::ShareRead::read(&share).as_ref().clone();
+                #editor_ident::new(share, value.into())
+            }
+        }
+    }
+}
+
+/// The types in the tuple holding the state of each field: an editor, a viewer for read-only
+/// fields, or the value itself for skipped fields.
+fn slot_types(record: &Record) -> Vec<TokenStream> {
+    record
+        .types()
+        .iter()
+        .zip(record.attrs())
+        .map(|(ty, attrs)| {
+            let task = if attrs.skip {
+                return quote!(#ty);
+            } else if attrs.readonly {
+                quote!(<#ty as ::top::task::view::View>::Task)
+            } else {
+                quote!(<#ty as ::top::task::edit::Edit>::Task)
+            };
+            match attrs.validates() {
+                true => quote!(::top::task::edit::Validate<#task>),
+                false => task,
+            }
+        })
+        .collect()
+}
+
+/// The types of the tasks for fields that are not skipped.
+fn task_types(record: &Record) -> Vec<TokenStream> {
+    slot_types(record)
+        .into_iter()
+        .zip(record.attrs())
+        .filter(|(_, attrs)| !attrs.skip)
+        .map(|(ty, _)| ty)
+        .collect()
+}
+
+/// The types of the fields that are skipped.
+fn skipped_types<'a>(record: &Record<'a>) -> Vec<&'a Type> {
+    record
+        .types()
+        .into_iter()
+        .zip(record.attrs())
+        .filter(|(_, attrs)| attrs.skip)
+        .map(|(ty, _)| ty)
+        .collect()
+}
+
+/// Creates the state for the fields in the record's [`bindings`](Record::bindings).
+fn start_tasks(record: &Record) -> TokenStream {
+    let slots = record
+        .types()
+        .into_iter()
+        .zip(record.attrs())
+        .zip(record.bindings())
+        .enumerate()
+        .map(|(index, ((ty, attrs), binding))| {
+            if attrs.skip {
+                quote!(#binding)
+            } else if attrs.readonly {
+                let task = quote!(<#ty as ::top::task::view::View>::view(#binding));
+                record.with_label(index, with_validation(attrs, task))
+            } else {
+                let task = quote!(<#ty as ::top::task::edit::Edit>::edit(Some(#binding)));
+                let task = with_validation(attrs, with_widget(attrs, task));
+                record.with_label(index, task)
+            }
+        });
+    quote!((#(#slots,)*))
+}
+
+/// Creates the state for the fields without a value, using their defaults if they have any.
+fn enter_tasks(record: &Record) -> TokenStream {
+    let slots = record
+        .types()
+        .into_iter()
+        .zip(record.attrs())
+        .enumerate()
+        .map(|(index, (ty, attrs))| {
+            let default = match &attrs.default {
+                None => quote!(::std::default::Default::default()),
+                Some(default) => quote!(#default),
+            };
+            if attrs.skip {
+                default
+            } else if attrs.readonly {
+                let task = quote!(<#ty as ::top::task::view::View>::view(#default));
+                record.with_label(index, with_validation(attrs, task))
+            } else {
+                let value = attrs.default.as_ref().map(|_| quote!(Some(#default)));
+                let value = value.unwrap_or_else(|| quote!(None));
+                let task = quote!(<#ty as ::top::task::edit::Edit>::edit(#value));
+                let task = with_validation(attrs, with_widget(attrs, task));
+                record.with_label(index, task)
+            }
+        });
+    quote!((#(#slots,)*))
+}
+
+/// Sets the widget of the editor created by `task`, if the field asks for one.
+fn with_widget(attrs: &FieldAttrs, task: TokenStream) -> TokenStream {
+    let widget = match attrs.widget {
+        None => return task,
+        Some(Widget::TextArea) => quote!(TextArea),
+        Some(Widget::Password) => quote!(Password),
+        Some(Widget::Slider) => quote!(Slider),
+    };
+    quote!(#task.with_widget(::top::task::edit::Widget::#widget))
+}
+
+/// Checks the value of the task created by `task` with the validators the field asks for.
+fn with_validation(attrs: &FieldAttrs, task: TokenStream) -> TokenStream {
+    if !attrs.validates() {
+        return task;
+    }
+
+    let mut task = quote!(::top::task::edit::Validate::new(#task));
+    if let Some(validate) = &attrs.validate {
+        task = quote!(#task.with(#validate));
+    }
+    if let Some(min) = &attrs.min {
+        task = quote!(#task.min(#min));
+    }
+    if let Some(max) = &attrs.max {
+        task = quote!(#task.max(#max));
+    }
+    if let Some(length) = &attrs.length {
+        let min = option(&length.min);
+        let max = option(&length.max);
+        task = quote!(#task.length(#min, #max));
+    }
+    if let Some(regex) = &attrs.regex {
+        task = quote!(#task.regex(#regex));
+    }
+    task
+}
+
+/// Turns an optional expression into an expression of type `Option`.
+fn option(expr: &Option<Expr>) -> TokenStream {
+    match expr {
+        None => quote!(None),
+        Some(expr) => quote!(Some(#expr)),
+    }
+}
+
+/// Bounds on the types of fields that mention the type's generic parameters, required to create
+/// their state.
+fn field_bounds(record: &Record, generic: &Generic) -> Vec<TokenStream> {
+    record
+        .types()
+        .into_iter()
+        .zip(record.attrs())
+        .zip(record.labels())
+        .filter(|((ty, _), _)| generic.mentions(ty))
+        .flat_map(|((ty, attrs), label)| {
+            let default = attrs
+                .default
+                .is_none()
+                .then(|| quote!(::std::default::Default));
+            let (task, bound) = if attrs.skip {
+                return default
+                    .map(|default| quote!(#ty: #default))
+                    .into_iter()
+                    .collect();
+            } else if attrs.readonly {
+                let default = default.map(|default| quote!(+ #default));
+                (
+                    quote!(<#ty as ::top::task::view::View>::Task),
+                    quote!(#ty: ::top::task::view::View #default),
+                )
+            } else {
+                (
+                    quote!(<#ty as ::top::task::edit::Edit>::Task),
+                    quote!(#ty: ::top::task::edit::Edit),
+                )
+            };
+            let label = label.map(|_| quote!(#task: ::top::task::WithLabel));
+            std::iter::once(bound).chain(label).collect::<Vec<_>>()
+        })
+        .collect()
+}
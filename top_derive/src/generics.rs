@@ -0,0 +1,162 @@
+use proc_macro2::{Ident, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{GenericParam, Generics, Type};
+
+/// The generics of a type deriving `Edit` or `View`, which are carried over to the generated task.
+/// The task is additionally generic over the share it works on, which comes right after the
+/// lifetimes.
+pub struct Generic<'a> {
+    generics: &'a Generics,
+    share: Ident,
+}
+
+impl<'a> Generic<'a> {
+    pub fn new(generics: &'a Generics) -> Self {
+        Generic {
+            generics,
+            share: format_ident!("__TopShare"),
+        }
+    }
+
+    /// The parameter for the share of the generated task, named such that it does not clash with
+    /// the parameters of the type or the types of its fields.
+    pub fn share(&self) -> &Ident {
+        &self.share
+    }
+
+    /// Parameters to declare a generated type or impl with, such as `<'a, __TopShare, T: Clone>`.
+    pub fn params(&self, share: bool) -> TokenStream {
+        let lifetimes = self.generics.lifetimes();
+        let share = share.then(|| self.share.to_token_stream());
+        let params = self
+            .generics
+            .params
+            .iter()
+            .filter(|param| !matches!(param, GenericParam::Lifetime(_)))
+            .map(|param| match param {
+                GenericParam::Type(param) => {
+                    let ident = &param.ident;
+                    let bounds = &param.bounds;
+                    match bounds.is_empty() {
+                        true => quote!(#ident),
+                        false => quote!(#ident: #bounds),
+                    }
+                }
+                GenericParam::Const(param) => {
+                    let ident = &param.ident;
+                    let ty = &param.ty;
+                    quote!(const #ident: #ty)
+                }
+                GenericParam::Lifetime(_) => unreachable!(),
+            });
+        let params: Vec<_> = lifetimes
+            .map(ToTokens::to_token_stream)
+            .chain(share)
+            .chain(params)
+            .collect();
+        match params.is_empty() {
+            true => TokenStream::new(),
+            false => quote!(<#(#params),*>),
+        }
+    }
+
+    /// Arguments to refer to `ident` with, passing `share` as the share if there is one, such as
+    /// `Editor<'a, __TopShare, T>`.
+    pub fn apply(&self, ident: &Ident, share: Option<TokenStream>) -> TokenStream {
+        let lifetimes = self
+            .generics
+            .lifetimes()
+            .map(|param| param.lifetime.to_token_stream());
+        let params = self.generics.params.iter().filter_map(|param| match param {
+            GenericParam::Type(param) => Some(param.ident.to_token_stream()),
+            GenericParam::Const(param) => Some(param.ident.to_token_stream()),
+            GenericParam::Lifetime(_) => None,
+        });
+        let args: Vec<_> = lifetimes.chain(share).chain(params).collect();
+        match args.is_empty() {
+            true => quote!(#ident),
+            false => quote!(#ident<#(#args),*>),
+        }
+    }
+
+    /// The predicates in the where clause of the type, each followed by a comma.
+    pub fn predicates(&self) -> TokenStream {
+        let predicates = self
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+        quote!(#(#predicates,)*)
+    }
+
+    /// Whether `ty` mentions any of the type's parameters, and therefore needs explicit bounds.
+    pub fn mentions(&self, ty: &Type) -> bool {
+        contains(ty.to_token_stream(), &self.idents(), &self.lifetimes())
+    }
+
+    /// Drops the types that mention any of the type's lifetimes but none of its type parameters.
+    /// Bounds on those types confuse the borrow checker in the futures of `async_trait` methods, so
+    /// they are left to the compiler to prove instead, which it can since they are not generic.
+    pub fn without_lifetimes<T: ToTokens>(&self, types: Vec<T>) -> Vec<T> {
+        let lifetimes = self.lifetimes();
+        let params: Vec<_> = self
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect();
+        types
+            .into_iter()
+            .filter(|ty| {
+                let tokens = ty.to_token_stream();
+                !contains(tokens.clone(), &[], &lifetimes) || contains(tokens, &params, &[])
+            })
+            .collect()
+    }
+
+    /// The identifiers of the type and const parameters of the type.
+    fn idents(&self) -> Vec<&Ident> {
+        self.generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(param) => Some(&param.ident),
+                GenericParam::Const(param) => Some(&param.ident),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect()
+    }
+
+    /// The identifiers of the lifetime parameters of the type, without their apostrophes.
+    fn lifetimes(&self) -> Vec<&Ident> {
+        self.generics
+            .lifetimes()
+            .map(|param| &param.lifetime.ident)
+            .collect()
+    }
+}
+
+/// Whether `tokens` contain any of the identifiers in `idents`, or any lifetime with an identifier
+/// in `lifetimes`. Identifiers are only compared to `idents` and lifetimes only to `lifetimes`, so
+/// that a path segment `a` does not match the lifetime `'a`.
+fn contains(tokens: TokenStream, idents: &[&Ident], lifetimes: &[&Ident]) -> bool {
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        let found = match token {
+            TokenTree::Punct(punct)
+                if punct.as_char() == '\'' && punct.spacing() == Spacing::Joint =>
+            {
+                match tokens.next() {
+                    Some(TokenTree::Ident(ident)) => lifetimes.contains(&&ident),
+                    _ => false,
+                }
+            }
+            TokenTree::Ident(ident) => idents.contains(&&ident),
+            TokenTree::Group(group) => contains(group.stream(), idents, lifetimes),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        };
+        if found {
+            return true;
+        }
+    }
+    false
+}
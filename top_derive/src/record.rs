@@ -0,0 +1,193 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Fields, Index, Result, Type};
+
+use crate::attr::FieldAttrs;
+
+/// The fields of a struct or enum variant, together with the path used to construct it.
+pub struct Record<'a> {
+    path: TokenStream,
+    fields: &'a Fields,
+    attrs: Vec<FieldAttrs>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(path: TokenStream, fields: &'a Fields) -> Result<Self> {
+        let attrs = fields
+            .iter()
+            .map(|field| FieldAttrs::parse(&field.attrs))
+            .collect::<Result<_>>()?;
+        Ok(Record {
+            path,
+            fields,
+            attrs,
+        })
+    }
+
+    /// Local variable names, one for each field.
+    pub fn bindings(&self) -> Vec<Ident> {
+        (0..self.fields.len())
+            .map(|index| format_ident!("field_{index}"))
+            .collect()
+    }
+
+    /// Tuple indices, one for each field.
+    pub fn indices(&self) -> Vec<Index> {
+        (0..self.fields.len()).map(Index::from).collect()
+    }
+
+    /// Tuple indices of the fields that are not skipped, and therefore have a task.
+    pub fn task_indices(&self) -> Vec<Index> {
+        self.attrs
+            .iter()
+            .enumerate()
+            .filter(|(_, attrs)| !attrs.skip)
+            .map(|(index, _)| Index::from(index))
+            .collect()
+    }
+
+    pub fn types(&self) -> Vec<&'a Type> {
+        self.fields.iter().map(|field| &field.ty).collect()
+    }
+
+    pub fn attrs(&self) -> &[FieldAttrs] {
+        &self.attrs
+    }
+
+    /// Labels of the fields: either set explicitly, or the humanized field name.
+    pub fn labels(&self) -> Vec<Option<String>> {
+        self.fields
+            .iter()
+            .zip(&self.attrs)
+            .map(|(field, attrs)| {
+                attrs
+                    .label
+                    .clone()
+                    .or_else(|| field.ident.as_ref().map(humanize))
+            })
+            .collect()
+    }
+
+    /// Pattern binding every field to its [`bindings`](Self::bindings), which doubles as the
+    /// expression that constructs the record from those bindings.
+    pub fn pattern(&self) -> TokenStream {
+        let path = &self.path;
+        let bindings = self.bindings();
+        match self.fields {
+            Fields::Named(fields) => {
+                let idents = fields.named.iter().map(|field| &field.ident);
+                quote!(#path { #(#idents: #bindings),* })
+            }
+            Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+            Fields::Unit => quote!(#path),
+        }
+    }
+
+    /// Assembles the record from the values of the field tasks in `tasks`, where skipped fields
+    /// hold their value directly.
+    pub fn value(&self, tasks: &TokenStream) -> TokenStream {
+        let pattern = self.pattern();
+        let values: Vec<_> = self
+            .indices()
+            .iter()
+            .zip(&self.attrs)
+            .map(|(index, attrs)| match attrs.skip {
+                true => quote!(::top::task::TaskValue::Unstable(#tasks.#index.clone())),
+                false => quote!(::top::task::Value::value(&#tasks.#index).await),
+            })
+            .collect();
+        let (combined, combined_pattern) = combine(&values, &self.bindings());
+
+        quote! {
+            match #combined {
+                ::top::task::TaskValue::Stable(#combined_pattern) => {
+                    ::top::task::TaskValue::Stable(#pattern)
+                }
+                ::top::task::TaskValue::Unstable(#combined_pattern) => {
+                    ::top::task::TaskValue::Unstable(#pattern)
+                }
+                ::top::task::TaskValue::Error(error) => ::top::task::TaskValue::Error(error),
+                ::top::task::TaskValue::Empty => ::top::task::TaskValue::Empty,
+            }
+        }
+    }
+
+    /// Passes `event` to all field tasks in `tasks`, merging their feedback.
+    pub fn on_event(&self, tasks: &TokenStream) -> TokenStream {
+        let indices = self.task_indices();
+        quote! {{
+            let feedback = ::top::html::event::Feedback::new();
+            #(
+                let field =
+                    ::top::html::Handler::on_event(&mut #tasks.#indices, event.clone()).await;
+                let feedback = feedback.combined_with(field);
+            )*
+            feedback
+        }}
+    }
+
+    /// Refreshes all field tasks in `tasks`, merging their feedback.
+    pub fn refresh(&self, tasks: &TokenStream) -> TokenStream {
+        let indices = self.task_indices();
+        quote! {{
+            let feedback = ::top::html::event::Feedback::new();
+            #(
+                let field = ::top::html::Refresh::refresh(&mut #tasks.#indices, ids).await;
+                let feedback = feedback.combined_with(field);
+            )*
+            feedback
+        }}
+    }
+
+    /// Renders the field tasks in `tasks` below each other.
+    pub fn to_html(&self, tasks: &TokenStream) -> TokenStream {
+        let indices = self.task_indices();
+        quote! {
+            vec![
+                #(::top::html::Html(format!(
+                    r#"<div class="field">{}</div>"#,
+                    ::top::html::ToHtml::to_html(&#tasks.#indices).await
+                )),)*
+            ]
+            .into_iter()
+            .collect::<::top::html::Html>()
+        }
+    }
+
+    /// Labels the task created by `task` for the field at `index`, if it has a label.
+    pub fn with_label(&self, index: usize, task: TokenStream) -> TokenStream {
+        match &self.labels()[index] {
+            None => task,
+            Some(label) => quote! {
+                ::top::task::WithLabel::with_label(#task, ::std::string::String::from(#label))
+            },
+        }
+    }
+}
+
+/// Combines task values into a single value using [`TaskValue::and`], returning the combining
+/// expression together with the (nested tuple) pattern that destructures its content into
+/// `bindings`.
+pub fn combine(values: &[TokenStream], bindings: &[Ident]) -> (TokenStream, TokenStream) {
+    let mut iter = values.iter().zip(bindings);
+    match iter.next() {
+        None => (quote!(::top::task::TaskValue::Unstable(())), quote!(())),
+        Some((value, binding)) => iter.fold(
+            (value.clone(), quote!(#binding)),
+            |(expr, pattern), (value, binding)| {
+                (quote!(#expr.and(#value)), quote!((#pattern, #binding)))
+            },
+        ),
+    }
+}
+
+/// Turns an identifier such as `first_name` into a label such as `First name`.
+pub fn humanize(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    let words = ident.trim_start_matches("r#").replace('_', " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
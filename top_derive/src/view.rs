@@ -0,0 +1,535 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Error, Result, Type};
+
+use crate::edit::{impl_with_label, label_html};
+use crate::generics::Generic;
+use crate::record::Record;
+
+pub fn impl_view(ast: DeriveInput) -> Result<TokenStream> {
+    match &ast.data {
+        Data::Struct(data_struct) => impl_view_struct(&ast, data_struct),
+        Data::Enum(data_enum) => impl_view_enum(&ast, data_enum),
+        Data::Union(_) => Err(Error::new_spanned(&ast, "unions are not supported")),
+    }
+}
+
+fn impl_view_struct(ast: &DeriveInput, data_struct: &DataStruct) -> Result<TokenStream> {
+    let ident = &ast.ident;
+    let vis = &ast.vis;
+    let viewer_ident = format_ident!("{ident}Viewer");
+    let generic = Generic::new(&ast.generics);
+    let share = generic.share();
+    let this = generic.apply(ident, None);
+    let viewer = generic.apply(&viewer_ident, Some(share.to_token_stream()));
+    let params = generic.params(true);
+
+    let record = Record::new(quote!(#ident), &data_struct.fields)?;
+    let predicates = generic.predicates();
+    let field_bounds = field_bounds(&record, &generic);
+    let bounds = quote!(#predicates #(#field_bounds,)*);
+    let pattern = record.pattern();
+    let field_types = generic.without_lifetimes(record.types());
+    let slots = slot_types(&record);
+    let slot_types = generic.without_lifetimes(slots.clone());
+    let task_types = generic.without_lifetimes(task_types(&record));
+    let skipped_types = generic.without_lifetimes(skipped_types(&record));
+    let tasks = quote!(tasks);
+    let start_tasks = start_tasks(&record);
+    let value = record.value(&tasks);
+    let on_event = record.on_event(&tasks);
+    let refresh = record.refresh(&tasks);
+    let to_html = record.to_html(&tasks);
+
+    let doc = format!("Viewer for [`{ident}`], generated by `#[derive(View)]`.");
+    let viewer_struct = quote! {
+        #[doc = #doc]
+        #vis struct #viewer_ident #params
+        where
+            #bounds
+        {
+            id: ::top::__private::Uuid,
+            share: #share,
+            label: Option<String>,
+            tasks: Option<(#(#slots,)*)>,
+        }
+
+        impl #params #viewer
+        where
+            #bounds
+        {
+            fn new(share: #share, value: Option<#this>) -> Self {
+                #viewer_ident {
+                    id: ::top::__private::Uuid::new_v4(),
+                    share,
+                    label: None,
+                    tasks: Self::tasks(value),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn tasks(value: Option<#this>) -> Option<(#(#slots,)*)> {
+                match value {
+                    Some(#pattern) => Some(#start_tasks),
+                    None => None,
+                }
+            }
+        }
+    };
+
+    let value_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::task::Value for #viewer
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#field_types: Send,)*
+            #(#slot_types: Send + Sync,)*
+            #(#skipped_types: Clone,)*
+        {
+            type Output = #this;
+
+            #[allow(unused_variables)]
+            async fn value(&self) -> ::top::task::TaskValue<Self::Output> {
+                match &self.tasks {
+                    Some(tasks) => #value,
+                    None => ::top::task::TaskValue::Empty,
+                }
+            }
+        }
+    };
+
+    let handler_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Handler for #viewer
+        where
+            #bounds
+            #share: Send,
+            #(#slot_types: Send,)*
+            #(#task_types: ::top::html::Handler + Send,)*
+        {
+            #[allow(unused_variables)]
+            async fn on_event(
+                &mut self,
+                event: ::top::html::event::Event,
+            ) -> ::top::html::event::Feedback {
+                match &mut self.tasks {
+                    Some(tasks) => #on_event,
+                    None => ::top::html::event::Feedback::new(),
+                }
+            }
+        }
+    };
+
+    let refresh_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Refresh for #viewer
+        where
+            #bounds
+            #share: ::top::share::ShareRead<Value = #this> + ::top::share::ShareUpdate + Send + Sync,
+            #this: Clone,
+            #viewer: ::top::html::ToHtml,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::Refresh + Send + Sync,)*
+        {
+            #[allow(unused_variables)]
+            async fn refresh(
+                &mut self,
+                ids: &::std::collections::BTreeSet<::top::__private::Uuid>,
+            ) -> ::top::html::event::Feedback {
+                if ::top::share::ShareUpdate::updated(&self.share, ids) {
+                    let value = ::top::share::ShareRead::read(&self.share).as_ref().clone();
+                    self.tasks = Self::tasks(value.into());
+                    ::top::html::event::Feedback::from(::top::html::event::Change::Replace {
+                        id: self.id,
+                        html: ::top::html::ToHtml::to_html(self).await,
+                    })
+                } else {
+                    match &mut self.tasks {
+                        Some(tasks) => #refresh,
+                        None => ::top::html::event::Feedback::new(),
+                    }
+                }
+            }
+        }
+    };
+
+    let label = label_html();
+    let to_html_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::ToHtml for #viewer
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::ToHtml + Send + Sync,)*
+        {
+            #[allow(unused_variables)]
+            async fn to_html(&self) -> ::top::html::Html {
+                let id = self.id;
+                let label = #label;
+                let fields = match &self.tasks {
+                    Some(tasks) => #to_html,
+                    None => ::top::html::Html::default(),
+                };
+                ::top::html::Html(format!(r#"<div id="{id}">{label}{fields}</div>"#))
+            }
+        }
+    };
+
+    let with_label_impl = impl_with_label(&params, &viewer, &bounds);
+    let view_impls = impl_view_traits(&generic, ident, &viewer_ident, &bounds);
+
+    Ok(quote! {
+        #viewer_struct
+        #value_impl
+        #handler_impl
+        #refresh_impl
+        #to_html_impl
+        #with_label_impl
+        #view_impls
+    })
+}
+
+fn impl_view_enum(ast: &DeriveInput, data_enum: &DataEnum) -> Result<TokenStream> {
+    let ident = &ast.ident;
+    let vis = &ast.vis;
+    let viewer_ident = format_ident!("{ident}Viewer");
+    let variant_ident = format_ident!("{ident}ViewerVariant");
+    let generic = Generic::new(&ast.generics);
+    let share = generic.share();
+    let this = generic.apply(ident, None);
+    let viewer = generic.apply(&viewer_ident, Some(share.to_token_stream()));
+    let variant = generic.apply(&variant_ident, None);
+    let params = generic.params(true);
+    let variant_params = generic.params(false);
+
+    let variant_idents: Vec<_> = data_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect();
+    let variant_names: Vec<_> = variant_idents
+        .iter()
+        .map(|variant| variant.to_string())
+        .collect();
+    let records: Vec<_> = data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+            Record::new(quote!(#ident::#variant_ident), &variant.fields)
+        })
+        .collect::<Result<_>>()?;
+    let predicates = generic.predicates();
+    let field_bounds: Vec<_> = records
+        .iter()
+        .flat_map(|record| field_bounds(record, &generic))
+        .collect();
+    let bounds = quote!(#predicates #(#field_bounds,)*);
+
+    let patterns: Vec<_> = records.iter().map(Record::pattern).collect();
+    let field_types = generic.without_lifetimes(records.iter().flat_map(Record::types).collect());
+    let slot_types = generic.without_lifetimes(records.iter().flat_map(slot_types).collect());
+    let task_types = generic.without_lifetimes(records.iter().flat_map(task_types).collect());
+    let skipped_types = generic.without_lifetimes(records.iter().flat_map(skipped_types).collect());
+    let variant_slot_types: Vec<_> = records
+        .iter()
+        .map(|record| {
+            let slot_types = self::slot_types(record);
+            quote!((#(#slot_types,)*))
+        })
+        .collect();
+    let tasks = quote!(tasks);
+    let start_tasks: Vec<_> = records.iter().map(start_tasks).collect();
+    let values: Vec<_> = records.iter().map(|record| record.value(&tasks)).collect();
+    let on_events: Vec<_> = records
+        .iter()
+        .map(|record| record.on_event(&tasks))
+        .collect();
+    let refreshes: Vec<_> = records
+        .iter()
+        .map(|record| record.refresh(&tasks))
+        .collect();
+    let to_htmls: Vec<_> = records
+        .iter()
+        .map(|record| record.to_html(&tasks))
+        .collect();
+
+    let doc = format!("Viewer for [`{ident}`], generated by `#[derive(View)]`.");
+    let variant_doc = format!("Field viewers for the variant of [`{ident}`].");
+    let viewer_struct = quote! {
+        #[doc = #doc]
+        #vis struct #viewer_ident #params
+        where
+            #bounds
+        {
+            id: ::top::__private::Uuid,
+            share: #share,
+            label: Option<String>,
+            variant: Option<#variant>,
+        }
+
+        #[doc = #variant_doc]
+        #[doc(hidden)]
+        #[allow(clippy::large_enum_variant)]
+        #vis enum #variant_ident #variant_params
+        where
+            #bounds
+        {
+            #(#variant_idents(#variant_slot_types),)*
+        }
+
+        impl #params #viewer
+        where
+            #bounds
+        {
+            fn new(share: #share, value: Option<#this>) -> Self {
+                #viewer_ident {
+                    id: ::top::__private::Uuid::new_v4(),
+                    share,
+                    label: None,
+                    variant: Self::variant(value),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn variant(value: Option<#this>) -> Option<#variant> {
+                match value {
+                    #(Some(#patterns) => Some(#variant_ident::#variant_idents(#start_tasks)),)*
+                    None => None,
+                }
+            }
+        }
+    };
+
+    let value_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::task::Value for #viewer
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#field_types: Send,)*
+            #(#slot_types: Send + Sync,)*
+            #(#skipped_types: Clone,)*
+        {
+            type Output = #this;
+
+            #[allow(unused_variables)]
+            async fn value(&self) -> ::top::task::TaskValue<Self::Output> {
+                match &self.variant {
+                    #(Some(#variant_ident::#variant_idents(tasks)) => #values,)*
+                    None => ::top::task::TaskValue::Empty,
+                }
+            }
+        }
+    };
+
+    let handler_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Handler for #viewer
+        where
+            #bounds
+            #share: Send,
+            #(#slot_types: Send,)*
+            #(#task_types: ::top::html::Handler + Send,)*
+        {
+            #[allow(unused_variables)]
+            async fn on_event(
+                &mut self,
+                event: ::top::html::event::Event,
+            ) -> ::top::html::event::Feedback {
+                match &mut self.variant {
+                    #(Some(#variant_ident::#variant_idents(tasks)) => #on_events,)*
+                    None => ::top::html::event::Feedback::new(),
+                }
+            }
+        }
+    };
+
+    let refresh_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::Refresh for #viewer
+        where
+            #bounds
+            #share: ::top::share::ShareRead<Value = #this> + ::top::share::ShareUpdate + Send + Sync,
+            #this: Clone,
+            #viewer: ::top::html::ToHtml,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::Refresh + Send + Sync,)*
+        {
+            #[allow(unused_variables)]
+            async fn refresh(
+                &mut self,
+                ids: &::std::collections::BTreeSet<::top::__private::Uuid>,
+            ) -> ::top::html::event::Feedback {
+                if ::top::share::ShareUpdate::updated(&self.share, ids) {
+                    let value = ::top::share::ShareRead::read(&self.share).as_ref().clone();
+                    self.variant = Self::variant(value.into());
+                    ::top::html::event::Feedback::from(::top::html::event::Change::Replace {
+                        id: self.id,
+                        html: ::top::html::ToHtml::to_html(self).await,
+                    })
+                } else {
+                    match &mut self.variant {
+                        #(Some(#variant_ident::#variant_idents(tasks)) => #refreshes,)*
+                        None => ::top::html::event::Feedback::new(),
+                    }
+                }
+            }
+        }
+    };
+
+    let label = label_html();
+    let to_html_impl = quote! {
+        #[::top::__private::async_trait]
+        impl #params ::top::html::ToHtml for #viewer
+        where
+            #bounds
+            #share: Send + Sync,
+            #(#slot_types: Send + Sync,)*
+            #(#task_types: ::top::html::ToHtml + Send + Sync,)*
+        {
+            #[allow(unused_variables)]
+            async fn to_html(&self) -> ::top::html::Html {
+                let id = self.id;
+                let label = #label;
+                let html = match &self.variant {
+                    #(Some(#variant_ident::#variant_idents(tasks)) => {
+                        let fields = #to_htmls;
+                        format!(r#"<p><strong>{}</strong></p>{fields}"#, #variant_names)
+                    })*
+                    None => String::new(),
+                };
+                ::top::html::Html(format!(r#"<div id="{id}">{label}{html}</div>"#))
+            }
+        }
+    };
+
+    let with_label_impl = impl_with_label(&params, &viewer, &bounds);
+    let view_impls = impl_view_traits(&generic, ident, &viewer_ident, &bounds);
+
+    Ok(quote! {
+        #viewer_struct
+        #value_impl
+        #handler_impl
+        #refresh_impl
+        #to_html_impl
+        #with_label_impl
+        #view_impls
+    })
+}
+
+/// Implements `View` and `ViewShared` for `ident` through its generated viewer.
+fn impl_view_traits(
+    generic: &Generic,
+    ident: &Ident,
+    viewer_ident: &Ident,
+    bounds: &TokenStream,
+) -> TokenStream {
+    let share = generic.share();
+    let this = generic.apply(ident, None);
+    let params = generic.params(false);
+    let shared_params = generic.params(true);
+    let viewer = generic.apply(viewer_ident, Some(share.to_token_stream()));
+    let owned_viewer = generic.apply(viewer_ident, Some(quote!(::top::share::ShareValue<#this>)));
+
+    quote! {
+        impl #params ::top::task::view::View for #this
+        where
+            #bounds
+            #owned_viewer: ::top::task::Value<Output = #this>,
+        {
+            type Task = #owned_viewer;
+
+            fn view(self) -> Self::Task {
+                #viewer_ident::new(::top::share::ShareValue::new(None), Some(self))
+            }
+        }
+
+        impl #shared_params ::top::task::view::ViewShared<#share> for #this
+        where
+            #bounds
+            #share: ::top::share::ShareRead<Value = #this> + Send + Sync,
+            #this: Clone,
+            #viewer: ::top::task::Value<Output = #this>,
+        {
+            type Task = #viewer;
+
+            fn view_shared(share: #share) -> Self::Task {
+                let value = ::top::share::ShareRead::read(&share).as_ref().clone();
+                #viewer_ident::new(share, value.into())
+            }
+        }
+    }
+}
+
+/// The types in the tuple holding the state of each field: a viewer, or the value itself for
+/// skipped fields.
+fn slot_types(record: &Record) -> Vec<TokenStream> {
+    record
+        .types()
+        .iter()
+        .zip(record.attrs())
+        .map(|(ty, attrs)| match attrs.skip {
+            true => quote!(#ty),
+            false => quote!(<#ty as ::top::task::view::View>::Task),
+        })
+        .collect()
+}
+
+/// The types of the viewers for fields that are not skipped.
+fn task_types(record: &Record) -> Vec<TokenStream> {
+    slot_types(record)
+        .into_iter()
+        .zip(record.attrs())
+        .filter(|(_, attrs)| !attrs.skip)
+        .map(|(ty, _)| ty)
+        .collect()
+}
+
+/// The types of the fields that are skipped.
+fn skipped_types<'a>(record: &Record<'a>) -> Vec<&'a Type> {
+    record
+        .types()
+        .into_iter()
+        .zip(record.attrs())
+        .filter(|(_, attrs)| attrs.skip)
+        .map(|(ty, _)| ty)
+        .collect()
+}
+
+/// Creates viewers for the fields in the record's [`bindings`](Record::bindings).
+fn start_tasks(record: &Record) -> TokenStream {
+    let slots = record
+        .types()
+        .into_iter()
+        .zip(record.attrs())
+        .zip(record.bindings())
+        .enumerate()
+        .map(|(index, ((ty, attrs), binding))| match attrs.skip {
+            true => quote!(#binding),
+            false => {
+                let task = quote!(<#ty as ::top::task::view::View>::view(#binding));
+                record.with_label(index, task)
+            }
+        });
+    quote!((#(#slots,)*))
+}
+
+/// Bounds on the types of fields that mention the type's generic parameters, required to view them.
+fn field_bounds(record: &Record, generic: &Generic) -> Vec<TokenStream> {
+    record
+        .types()
+        .into_iter()
+        .zip(record.attrs())
+        .zip(record.labels())
+        .filter(|((ty, attrs), _)| !attrs.skip && generic.mentions(ty))
+        .flat_map(|((ty, _), label)| {
+            let bound = quote!(#ty: ::top::task::view::View);
+            let label = label
+                .map(|_| quote!(<#ty as ::top::task::view::View>::Task: ::top::task::WithLabel));
+            std::iter::once(bound).chain(label)
+        })
+        .collect()
+}
@@ -43,6 +43,57 @@ function press(button: HTMLButtonElement) {
   console.log(`sent: ${message}`);
 }
 
+/**
+ * The number of bytes of a file that are sent in a single message.
+ */
+const CHUNK_SIZE = 64 * 1024;
+
+/**
+ * Sends the chosen file in chunks of binary messages, each consisting of a
+ * JSON header, a zero byte and the content of the chunk. Files larger than the
+ * `data-max` attribute of the input are refused without sending them.
+ * @param {HTMLInputElement} input The file input that was changed.
+ */
+async function upload(input: HTMLInputElement) {
+  const file = input.files?.item(0);
+  if (file == null) {
+    return;
+  }
+  input.classList.remove('is-success');
+  input.classList.remove('is-danger');
+  const max = input.dataset.max;
+  if (max != null && file.size > Number(max)) {
+    input.classList.add('is-danger');
+    const name = document.getElementById(input.dataset.name ?? '');
+    const message = document.getElementById(input.dataset.message ?? '');
+    if (name != null) {
+      name.textContent = '';
+    }
+    if (message != null) {
+      message.textContent = `\`${file.name}\` is larger than the limit of ${max} bytes`;
+    }
+    return;
+  }
+  input.classList.add('is-loading');
+  let offset = 0;
+  do {
+    const content = await file.slice(offset, offset + CHUNK_SIZE).arrayBuffer();
+    const header = new TextEncoder().encode(JSON.stringify({
+      id: input.id,
+      name: file.name,
+      mime: file.type || 'application/octet-stream',
+      size: file.size,
+      offset: offset,
+    }));
+    const message = new Uint8Array(header.length + 1 + content.byteLength);
+    message.set(header);
+    message.set(new Uint8Array(content), header.length + 1);
+    socket.send(message);
+    offset += content.byteLength;
+  } while (offset < file.size);
+  console.log(`sent: ${file.name} (${file.size} bytes)`);
+}
+
 /**
  * @param {Event} ev The event.
  */
@@ -75,6 +126,15 @@ function onMessage(ev: MessageEvent) {
     } else if (change.remove != null) {
       const element = document.getElementById(change.remove.id);
       element?.parentElement?.removeChild(element);
+    } else if (change.swap != null) {
+      const element = document.getElementById(change.swap.id);
+      const other = document.getElementById(change.swap.other);
+      if (element != null && other != null) {
+        const marker = document.createComment('');
+        element.replaceWith(marker);
+        other.replaceWith(element);
+        marker.replaceWith(other);
+      }
     } else if (change.valid != null) {
       const id = change.valid.id;
       const input = document.getElementById(id);